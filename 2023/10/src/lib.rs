@@ -1,3 +1,28 @@
 pub mod part_1;
 pub mod part_2;
 pub mod utils;
+
+use common::errors::AocError;
+use common::grid::Grid;
+use common::input::Input;
+use common::solution::Solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Grid<utils::Tile>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        utils::parse_input(input)
+    }
+
+    fn part_1(grid: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        part_1::solve(grid)
+    }
+
+    fn part_2(grid: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        part_2::solve(grid)
+    }
+}
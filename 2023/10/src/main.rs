@@ -1,6 +1,5 @@
-use aoc_2023_10::{part_1, part_2};
-use common::cli;
+use aoc_2023_10::Day10;
 
 fn main() {
-    cli::run(part_1::solve, part_2::solve);
+    common::run!(Day10);
 }
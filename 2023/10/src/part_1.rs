@@ -1,26 +1,35 @@
-use common::errors::AocError;
+use common::{debug, errors::AocError, grid::Grid};
 
-use crate::utils;
+use crate::utils::Tile;
 
-pub fn solve(filename: &str) -> Result<u64, AocError> {
-    let grid = utils::parse_input(filename);
-    println!("{grid:?}");
-    Ok(42)
+pub fn solve(grid: &Grid<Tile>) -> Result<u64, AocError> {
+    debug!("{grid:?}");
+    Err(AocError::NotImplemented)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::parse_input;
+    use common::input::InputSource;
 
     #[test]
+    #[ignore = "part 1 is not solved yet"]
     fn test_sample_1() {
-        let result = solve("sample_1").unwrap();
+        let input = InputSource::Bytes(include_bytes!("../sample_1"))
+            .read()
+            .unwrap();
+        let result = solve(&parse_input(&input).unwrap()).unwrap();
         assert_eq!(result, 4);
     }
 
     #[test]
+    #[ignore = "part 1 is not solved yet"]
     fn test_sample_2() {
-        let result = solve("sample_2").unwrap();
+        let input = InputSource::Bytes(include_bytes!("../sample_2"))
+            .read()
+            .unwrap();
+        let result = solve(&parse_input(&input).unwrap()).unwrap();
         assert_eq!(result, 8);
     }
 }
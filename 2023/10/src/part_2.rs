@@ -1,5 +1,8 @@
 use common::errors::AocError;
+use common::grid::Grid;
 
-pub fn solve(filename: &str) -> Result<u64, AocError> {
-    Ok(42)
+use crate::utils::Tile;
+
+pub fn solve(_grid: &Grid<Tile>) -> Result<u64, AocError> {
+    Err(AocError::NotImplemented)
 }
@@ -1,9 +1,10 @@
 use common::errors::AocError;
-use common::input::InputReader;
+use common::grid::Grid;
+use common::input::Input;
 
 #[derive(Clone, Copy, Debug)]
 #[repr(u8)]
-pub(crate) enum Tile {
+pub enum Tile {
     NS = b'|',
     EW = b'-',
     NE = b'L',
@@ -15,11 +16,11 @@ pub(crate) enum Tile {
     _X,
 }
 
-impl TryFrom<&char> for Tile {
+impl TryFrom<char> for Tile {
     type Error = AocError;
 
-    fn try_from(value: &char) -> Result<Self, Self::Error> {
-        match *value {
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
             '|' => Ok(Self::NS),
             '-' => Ok(Self::EW),
             'L' => Ok(Self::NE),
@@ -28,18 +29,11 @@ impl TryFrom<&char> for Tile {
             'F' => Ok(Self::SE),
             '.' => Ok(Self::G_),
             'S' => Ok(Self::S_),
-            _ => Err(AocError::Parse(format!(
-                "Invalid tile character: {}",
-                value
-            ))),
+            _ => Err(AocError::Parse("expected one of '|-LJ7F.S'".to_string())),
         }
     }
 }
 
-pub(crate) fn parse_input(filename: &str) -> Vec<Vec<Tile>> {
-    InputReader::as_char_grid(filename)
-        .unwrap()
-        .iter()
-        .map(|row| row.iter().map(|e| Tile::try_from(e).unwrap()).collect())
-        .collect()
+pub fn parse_input(input: &Input) -> Result<Grid<Tile>, AocError> {
+    Ok(input.as_grid()?)
 }
@@ -0,0 +1,5 @@
+use common::{errors::AocError, input::Input};
+
+pub fn parse_input(input: &Input) -> Result<Vec<String>, AocError> {
+    Ok(input.as_lines()?)
+}
@@ -1,46 +1,43 @@
-use crate::utils::{
-    has_adjacent_symbol, print_colored_grid, print_grid_section, read_numbers, Number,
-};
-use common::{
-    color::Color,
-    input::{InputError, InputReader},
-};
+use crate::utils::{has_adjacent_symbol, log_colored_grid, log_grid_section, read_numbers, Number};
+use common::{color::Color, errors::AocError, grid::Grid, info, trace};
 
-pub fn solve(filename: &str) -> Result<i32, InputError> {
-    let grid = InputReader::as_char_grid(filename)?;
-    println!("Grid size: {} x {}", grid.len(), grid[0].len());
+pub fn solve(grid: &Grid<char>) -> Result<i32, AocError> {
+    info!("Grid size: {} x {}", grid.height(), grid.width());
 
-    let numbers: Vec<Number> = read_numbers(&grid);
-    println!("Found {} numbers in total", numbers.len());
+    let numbers: Vec<Number> = read_numbers(grid);
+    info!("Found {} numbers in total", numbers.len());
 
     let mut sum = 0;
     let mut part_numbers = Vec::new();
     let mut non_part_numbers = Vec::new();
 
     for number in &numbers {
-        if let Some(symbol) = has_adjacent_symbol(&grid, number) {
+        if let Some(symbol) = has_adjacent_symbol(grid, number) {
             sum += number.value;
             part_numbers.push(number.clone());
 
-            print_grid_section(&grid, number, Some(&symbol));
-            print!(
-                "\n{} Found symbol {} at position [{}, {}]",
+            log_grid_section(grid, number, Some(&symbol));
+            trace!(
+                "{} Found symbol {} at position [{}, {}]\t| Current sum: {}",
                 Color::Green.wrap("✓"),
                 Color::Red.wrap(&symbol.value.to_string()),
                 Color::Blue.wrap(&symbol.row.to_string()),
-                Color::Blue.wrap(&symbol.col.to_string())
+                Color::Blue.wrap(&symbol.col.to_string()),
+                Color::Yellow.wrap(&sum.to_string())
             );
         } else {
             non_part_numbers.push(number.clone());
 
-            print_grid_section(&grid, number, None);
-            print!("\n{} No adjacent symbol found", Color::Red.wrap("✗"));
+            log_grid_section(grid, number, None);
+            trace!(
+                "{} No adjacent symbol found\t| Current sum: {}",
+                Color::Red.wrap("✗"),
+                Color::Yellow.wrap(&sum.to_string())
+            );
         }
-        print!("\t| Current sum: {}", Color::Yellow.wrap(&sum.to_string()));
-        println!("\n----------------------------------------------------------\n");
     }
 
-    print_colored_grid(&grid, &part_numbers, &non_part_numbers, None);
+    log_colored_grid(grid, &part_numbers, &non_part_numbers, None);
 
     Ok(sum)
 }
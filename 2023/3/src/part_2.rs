@@ -1,33 +1,22 @@
 use crate::utils::{find_all_adjacent_numbers, Symbol};
-use common::{
-    color::Color,
-    input::{InputError, InputReader},
-};
+use common::{color::Color, debug, errors::AocError, grid::Grid};
 
-pub fn solve(filename: &str) -> Result<i32, InputError> {
-    let grid = InputReader::as_char_grid(filename)?;
+pub fn solve(grid: &Grid<char>) -> Result<i32, AocError> {
     let mut sum = 0;
 
-    let mut gear_symbols = Vec::new();
-    for (row, line) in grid.iter().enumerate() {
-        for (col, &c) in line.iter().enumerate() {
-            if c == '*' {
-                gear_symbols.push(Symbol {
-                    value: '*',
-                    row,
-                    col,
-                });
-            }
-        }
-    }
+    let gear_symbols = grid.positions(|&c| c == '*').map(|point| Symbol {
+        value: '*',
+        row: point.y,
+        col: point.x,
+    });
 
     for symbol in gear_symbols {
-        let adjacent_numbers = find_all_adjacent_numbers(&grid, &symbol);
+        let adjacent_numbers = find_all_adjacent_numbers(grid, &symbol);
 
         if adjacent_numbers.len() == 2 {
             let gear_ratio = adjacent_numbers[0].value * adjacent_numbers[1].value;
             sum += gear_ratio;
-            println!(
+            debug!(
                 "Found gear at [{}, {}] with ratio {}",
                 Color::Blue.wrap(&symbol.row.to_string()),
                 Color::Blue.wrap(&symbol.col.to_string()),
@@ -1,4 +1,10 @@
-use common::color::Color;
+use common::grid::{Grid, Point};
+use common::log::{self, Level};
+use common::{color::Color, debug, errors::AocError, input::Input, trace};
+
+pub fn parse_input(input: &Input) -> Result<Grid<char>, AocError> {
+    Ok(input.as_char_grid()?)
+}
 
 #[derive(Debug, Clone)]
 pub struct Number {
@@ -21,136 +27,72 @@ pub fn is_symbol(c: char) -> bool {
 }
 
 #[must_use]
-pub fn has_adjacent_symbol(grid: &[Vec<char>], number: &Number) -> Option<Symbol> {
-    let n_rows = grid.len();
-    let n_cols = grid[0].len();
-
-    let row_start = number.row.saturating_sub(1);
-    let row_end = (number.row + 1).min(n_rows - 1);
-    let rows = row_start..=row_end;
-
-    for row in rows {
-        let col_start = number.start_col.saturating_sub(1);
-        let col_end = (number.end_col + 1).min(n_cols - 1);
-        let cols = col_start..=col_end;
-
-        for col in cols {
-            let char = grid[row][col];
-
-            if is_symbol(char) {
-                return Some(Symbol {
-                    value: char,
-                    row,
-                    col,
-                });
-            };
-        }
-    }
-    None
+pub fn has_adjacent_symbol(grid: &Grid<char>, number: &Number) -> Option<Symbol> {
+    (number.start_col..=number.end_col)
+        .flat_map(|col| grid.neighbors8(Point::new(col, number.row)))
+        .find_map(|point| {
+            let value = grid[point];
+            is_symbol(value).then_some(Symbol {
+                value,
+                row: point.y,
+                col: point.x,
+            })
+        })
 }
 
 #[must_use]
-pub fn has_adjacent_number(grid: &[Vec<char>], symbol: &Symbol) -> Option<Number> {
-    let mut current_number = String::new();
-
-    let n_rows = grid.len();
-    let n_cols = grid[0].len();
-
-    let row_start = symbol.row.saturating_sub(1);
-    let row_end = (symbol.row + 1).min(n_rows - 1);
-    let rows = row_start..=row_end;
-
-    for row in rows {
-        current_number.clear();
-
-        let col_start = symbol.col.saturating_sub(1);
-        let col_end = (symbol.col + 1).min(n_cols - 1);
-        let cols = col_start..=col_end;
-
-        for col in cols {
-            let c = grid[row][col];
-            if c.is_ascii_digit() {
-                current_number.push(c);
-                println!("Found digit {c}");
-                println!("Curr number: {current_number}");
-            } else if !current_number.is_empty() {
-                println!("Got to the end of a number, returning {current_number}");
-                return Some(Number {
-                    value: current_number.parse().unwrap(),
-                    row,
-                    start_col: col - current_number.len(),
-                    end_col: col - 1,
-                });
-            }
-        }
-        if !current_number.is_empty() {
-            println!("Got to the end of the line, returning {current_number}");
-            return Some(Number {
-                value: current_number.parse().unwrap(),
-                row,
-                start_col: n_cols - current_number.len(),
-                end_col: n_cols - 1,
-            });
-        }
+pub fn has_adjacent_number(grid: &Grid<char>, symbol: &Symbol) -> Option<Number> {
+    let number = find_all_adjacent_numbers(grid, symbol).into_iter().next();
+    if number.is_none() {
+        trace!("Did not find any numbers");
     }
-    println!("Did not find any numbers");
-    None
+    number
 }
 
 #[must_use]
-pub fn find_all_adjacent_numbers(grid: &[Vec<char>], symbol: &Symbol) -> Vec<Number> {
-    let n_rows = grid.len();
-    let n_cols = grid[0].len();
-
-    let row_start = symbol.row.saturating_sub(1);
-    let row_end = (symbol.row + 1).min(n_rows - 1);
-
-    let mut adjacent_numbers = Vec::new();
-    let mut visited_positions = Vec::new();
-
-    (row_start..=row_end).for_each(|row| {
-        let col_start = symbol.col.saturating_sub(1);
-        let col_end = (symbol.col + 1).min(n_cols - 1);
-
-        for col in col_start..=col_end {
-            if !grid[row][col].is_ascii_digit() || visited_positions.contains(&(row, col)) {
-                continue;
-            }
-
-            let mut start_col = col;
-            while start_col > 0 && grid[row][start_col - 1].is_ascii_digit() {
-                start_col -= 1;
-            }
-
-            let mut end_col = col;
-            while end_col < n_cols - 1 && grid[row][end_col + 1].is_ascii_digit() {
-                end_col += 1;
-            }
-
-            for i in start_col..=end_col {
-                visited_positions.push((row, i));
-            }
-
-            let number_str: String = (start_col..=end_col).map(|i| grid[row][i]).collect();
-            let value = number_str.parse().unwrap();
-
-            adjacent_numbers.push(Number {
-                value,
-                row,
-                start_col,
-                end_col,
-            });
+pub fn find_all_adjacent_numbers(grid: &Grid<char>, symbol: &Symbol) -> Vec<Number> {
+    let mut adjacent_numbers: Vec<Number> = Vec::new();
+
+    for point in grid.neighbors8(Point::new(symbol.col, symbol.row)) {
+        let already_found = adjacent_numbers.iter().any(|number| {
+            number.row == point.y && (number.start_col..=number.end_col).contains(&point.x)
+        });
+        if grid[point].is_ascii_digit() && !already_found {
+            adjacent_numbers.push(number_at(grid, point));
         }
-    });
+    }
     adjacent_numbers
 }
 
+/// The whole number that the digit at `point` is part of
+fn number_at(grid: &Grid<char>, point: Point) -> Number {
+    let line = grid.row(point.y);
+    let start_col = line[..point.x]
+        .iter()
+        .rposition(|c| !c.is_ascii_digit())
+        .map_or(0, |col| col + 1);
+    let end_col = line[point.x..]
+        .iter()
+        .position(|c| !c.is_ascii_digit())
+        .map_or(line.len(), |len| point.x + len)
+        - 1;
+
+    let digits: String = line[start_col..=end_col].iter().collect();
+    trace!("Found number {digits} around [{}, {}]", point.y, point.x);
+    Number {
+        value: digits.parse().unwrap(),
+        row: point.y,
+        start_col,
+        end_col,
+    }
+}
+
 #[must_use]
-pub fn read_numbers(grid: &[Vec<char>]) -> Vec<Number> {
+pub fn read_numbers(grid: &Grid<char>) -> Vec<Number> {
     let mut numbers = Vec::new();
     let mut current_number = String::new();
 
-    for (row, line) in grid.iter().enumerate() {
+    for (row, line) in grid.rows().enumerate() {
         current_number.clear();
         for (col, &c) in line.iter().enumerate() {
             if c.is_ascii_digit() {
@@ -178,28 +120,31 @@ pub fn read_numbers(grid: &[Vec<char>]) -> Vec<Number> {
     numbers
 }
 
-pub fn print_grid_section(grid: &[Vec<char>], number: &Number, symbol: Option<&Symbol>) {
-    let rows = grid.len();
-    let cols = grid[0].len();
+/// Logs the neighbourhood of `number` at trace level, highlighting `symbol`
+pub fn log_grid_section(grid: &Grid<char>, number: &Number, symbol: Option<&Symbol>) {
+    if !log::enabled(Level::Trace) {
+        return;
+    }
+
+    let rows = grid.height();
+    let cols = grid.width();
 
     let start_row = number.row.saturating_sub(1);
     let end_row = (number.row + 1).min(rows - 1);
     let start_col = number.start_col.saturating_sub(1);
     let end_col = (number.end_col + 1).min(cols - 1);
 
-    println!(
-        "Number: {} @ [{}, {}:{}]\n",
+    trace!(
+        "Number: {} @ [{}, {}:{}]",
         Color::Blue.wrap(&number.value.to_string()),
         Color::Blue.wrap(&number.row.to_string()),
         Color::Blue.wrap(&number.start_col.to_string()),
         Color::Blue.wrap(&number.end_col.to_string()),
     );
 
-    for (row, _) in grid.iter().enumerate().take(end_row + 1).skip(start_row) {
+    for (row, cells) in grid.rows().enumerate().take(end_row + 1).skip(start_row) {
         let mut line = String::new();
-        for col in start_col..=end_col {
-            let c = grid[row][col];
-
+        for (col, &c) in cells.iter().enumerate().take(end_col + 1).skip(start_col) {
             let color = if let Some(Symbol {
                 value: _,
                 row: symbol_row,
@@ -221,17 +166,22 @@ pub fn print_grid_section(grid: &[Vec<char>], number: &Number, symbol: Option<&S
 
             line.push_str(&color.wrap(&c.to_string()));
         }
-        println!("\t{line}");
+        trace!("\t{line}");
     }
 }
 
-pub fn print_colored_grid(
-    grid: &[Vec<char>],
+/// Logs the whole grid at debug level, with part and non-part numbers colored
+pub fn log_colored_grid(
+    grid: &Grid<char>,
     part_numbers: &[Number],
     non_part_numbers: &[Number],
     mult_part_numbers: Option<&[Number]>,
 ) {
-    for (row, line) in grid.iter().enumerate() {
+    if !log::enabled(Level::Debug) {
+        return;
+    }
+
+    for (row, line) in grid.rows().enumerate() {
         let mut colored_line = row.to_string() + ":\t";
         let mut current_col = 0;
 
@@ -285,7 +235,6 @@ pub fn print_colored_grid(
 
             current_col += 1;
         }
-        println!("{colored_line}");
+        debug!("{colored_line}");
     }
-    println!();
 }
@@ -1,19 +1,12 @@
 use crate::utils::Card;
-use common::errors::AocError;
-use common::input::InputReader;
-use std::str::FromStr;
+use common::{errors::AocError, trace};
 
-pub fn solve(filename: &str) -> Result<u32, AocError> {
-    let lines = InputReader::as_lines(filename)?;
-
-    let mut cards: Vec<Card> = lines
-        .iter()
-        .map(|line| Card::from_str(line).unwrap())
-        .collect::<Vec<_>>();
+pub fn solve(cards: &[Card]) -> Result<u32, AocError> {
+    let mut cards = cards.to_vec();
 
     process_cards(&mut cards, 0);
 
-    let total_cards: u32 = cards.iter().map(|card| u32::from(card.copies)).sum();
+    let total_cards: u32 = cards.iter().map(|card| card.copies).sum();
     Ok(total_cards)
 }
 
@@ -24,7 +17,7 @@ fn process_cards(cards: &mut [Card], card_idx: usize) {
 
     let matches = cards[card_idx].count_matches();
     let copies = cards[card_idx].copies;
-    println!("{:?}", cards[card_idx]);
+    trace!("{:?}", cards[card_idx]);
 
     for i in 1..=matches as usize {
         if card_idx + i < cards.len() {
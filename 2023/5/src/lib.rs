@@ -1,3 +1,27 @@
 pub mod part_1;
 pub mod part_2;
 pub mod utils;
+
+use common::errors::AocError;
+use common::input::Input;
+use common::solution::Solution;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = utils::Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        utils::parse_input(input)
+    }
+
+    fn part_1(almanac: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        part_1::solve(almanac)
+    }
+
+    fn part_2(almanac: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        part_2::solve(almanac)
+    }
+}
@@ -1,6 +1,6 @@
 use common::errors::AocError;
 
-use crate::utils::{parse_input, CategoryMap};
+use crate::utils::{Almanac, CategoryMap};
 
 fn find_lowest_location(seeds: &[u64], maps: &[CategoryMap]) -> u64 {
     let mut lowest_location = u64::MAX;
@@ -15,9 +15,6 @@ fn find_lowest_location(seeds: &[u64], maps: &[CategoryMap]) -> u64 {
     lowest_location
 }
 
-pub fn solve(filename: &str) -> Result<u32, AocError> {
-    let (seeds, maps) = parse_input(filename)?;
-    Ok(u32::try_from(find_lowest_location(&seeds, &maps))
-        .ok()
-        .expect("Lowest value is larger than u32"))
+pub fn solve(almanac: &Almanac) -> Result<u64, AocError> {
+    Ok(find_lowest_location(&almanac.seeds, &almanac.maps))
 }
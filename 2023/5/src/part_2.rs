@@ -1,6 +1,6 @@
 use common::errors::AocError;
 
-use crate::utils::{parse_input, CategoryMap, RangeMapping};
+use crate::utils::{Almanac, CategoryMap, RangeMapping};
 
 #[derive(Debug, Clone)]
 pub struct Range {
@@ -50,9 +50,8 @@ fn apply_mapping_rule(range: &Range, mapping: &RangeMapping) -> (Option<Range>,
     let overlap_end = range.end().min(mapping_end);
     let overlap_length = overlap_end - overlap_start + 1;
 
-    let offset = mapping.dest_start as i64 - mapping.source_start as i64;
     let transformed = Range {
-        start: (overlap_start as i64 + offset) as u64,
+        start: overlap_start - mapping.source_start + mapping.dest_start,
         length: overlap_length,
     };
 
@@ -96,19 +95,15 @@ fn find_lowest_location(seed_ranges: Vec<Range>, maps: &[CategoryMap]) -> u64 {
         .min()
         .unwrap_or(u64::MAX)
 }
-pub fn solve(filename: &str) -> Result<u32, AocError> {
-    let (seeds, maps) = parse_input(filename)?;
-
+pub fn solve(almanac: &Almanac) -> Result<u64, AocError> {
     // Convert seed values to ranges
     let mut seed_ranges = Vec::new();
-    for chunk in seeds.chunks_exact(2) {
+    for chunk in almanac.seeds.chunks_exact(2) {
         seed_ranges.push(Range {
             start: chunk[0],
             length: chunk[1],
         });
     }
 
-    Ok(u32::try_from(find_lowest_location(seed_ranges, &maps))
-        .ok()
-        .expect("Lowest value is larger than u32"))
+    Ok(find_lowest_location(seed_ranges, &almanac.maps))
 }
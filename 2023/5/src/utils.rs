@@ -1,4 +1,4 @@
-use common::{errors::AocError, input::InputReader};
+use common::{errors::AocError, input::Input, numbers};
 
 pub struct RangeMapping {
     pub dest_start: u64,
@@ -11,6 +11,7 @@ pub struct CategoryMap {
 }
 
 impl CategoryMap {
+    #[must_use]
     pub fn convert(&self, source: u64) -> u64 {
         for mapping in &self.mappings {
             if source >= mapping.source_start
@@ -23,46 +24,49 @@ impl CategoryMap {
     }
 }
 
-pub fn parse_input(filename: &str) -> Result<(Vec<u64>, Vec<CategoryMap>), AocError> {
-    let mut blocks = InputReader::as_paragraphs(filename)?.into_iter();
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<CategoryMap>,
+}
+
+pub fn parse_input(input: &Input) -> Result<Almanac, AocError> {
+    let text = input.as_string();
+    // Read from the whole input, so that errors name the line of the input
+    let numbers = numbers::unsigned_per_line::<u64>(text)?;
+    let mut lines = text.lines().zip(numbers);
 
-    let seeds_section = blocks
+    let (seeds_line, seeds) = lines
         .next()
         .ok_or_else(|| AocError::from("Missing seeds section"))?;
-    let seeds: Vec<u64> = seeds_section
-        .strip_prefix("seeds: ")
-        .ok_or_else(|| AocError::from("Invalid seeds format"))?
-        .split_whitespace()
-        .map(|s| s.parse().map_err(AocError::from))
-        .collect::<Result<_, _>>()?;
-
-    let mut maps = Vec::new();
-    for block in blocks {
-        let mut lines = block.lines();
-        lines.next();
-
-        let mut mappings = Vec::new();
-        for line in lines {
-            if line.trim().is_empty() {
-                continue;
-            }
-
-            let nums: Vec<u64> = line
-                .split_whitespace()
-                .map(|s| s.parse().map_err(AocError::from))
-                .collect::<Result<_, _>>()?;
-
-            if nums.len() != 3 {
-                return Err(AocError::from(format!("Invalid mapping line: {}", line)));
-            }
+    if !seeds_line.starts_with("seeds: ") {
+        return Err(AocError::from("Invalid seeds format"));
+    }
 
-            mappings.push(RangeMapping {
-                dest_start: nums[0],
-                source_start: nums[1],
-                range_length: nums[2],
+    let mut maps: Vec<CategoryMap> = Vec::new();
+    // The first line of each paragraph names the map, the others are its mappings
+    let mut paragraph_start = false;
+    for (line, nums) in lines {
+        if line.trim().is_empty() {
+            paragraph_start = true;
+            continue;
+        }
+        if paragraph_start {
+            maps.push(CategoryMap {
+                mappings: Vec::new(),
             });
+            paragraph_start = false;
+            continue;
         }
-        maps.push(CategoryMap { mappings });
+
+        let (Some(map), &[dest_start, source_start, range_length]) = (maps.last_mut(), &nums[..])
+        else {
+            return Err(AocError::from(format!("Invalid mapping line: {line}")));
+        };
+        map.mappings.push(RangeMapping {
+            dest_start,
+            source_start,
+            range_length,
+        });
     }
-    Ok((seeds, maps))
+    Ok(Almanac { seeds, maps })
 }
@@ -1,57 +1,62 @@
-use common::{errors::AocError, input::InputReader};
+use common::{errors::AocError, input::Input, numbers};
 
-pub fn parse_input(filename: &str) -> Result<(Vec<u32>, Vec<u32>), AocError> {
-    let lines = InputReader::as_lines(&filename)?;
-    let times = parse_line(&lines[0], "Time")?;
-    let distances = parse_line(&lines[1], "Distance")?;
-    Ok((times, distances))
+pub struct Races {
+    pub times: Vec<u32>,
+    pub distances: Vec<u32>,
 }
 
-fn parse_line(line: &str, prefix: &str) -> Result<Vec<u32>, AocError> {
-    return line
-        .strip_prefix(&format!("{}: ", prefix))
-        .ok_or_else(|| AocError::from(format!("Invalid '{prefix}' Section")))?
-        .split_whitespace()
-        .map(|n| n.parse().map_err(AocError::from))
-        .collect();
+pub fn parse_input(input: &Input) -> Result<Races, AocError> {
+    let lines = input.as_lines()?;
+    let times = parse_line(&lines[0], 1, "Time")?;
+    let distances = parse_line(&lines[1], 2, "Distance")?;
+    Ok(Races { times, distances })
 }
 
-fn calc_distance(held_time: &u32, max_time: &u32) -> u32 {
-    return (max_time - held_time) * held_time;
+/// Parses line `line_number` of the input, from 1
+fn parse_line(line: &str, line_number: usize, prefix: &str) -> Result<Vec<u32>, AocError> {
+    let numbers = line
+        .strip_prefix(&format!("{prefix}: "))
+        .ok_or_else(|| AocError::from(format!("Invalid '{prefix}' Section")))?;
+    let column = line.len() - numbers.len() + 1;
+    Ok(numbers::unsigned_at(numbers, line_number, column)?)
 }
 
-pub(crate) fn get_time_distances(max_time: &u32) -> Vec<(u32, u32)> {
-    return (0..=*max_time)
-        .map(|held_time| (held_time, calc_distance(&held_time, &max_time)))
-        .collect();
+fn calc_distance(held_time: u32, max_time: u32) -> u32 {
+    (max_time - held_time) * held_time
 }
 
-pub(crate) fn get_winners(time_distances: &Vec<(u32, u32)>, distance_to_beat: &u32) -> Vec<u32> {
+pub(crate) fn get_time_distances(max_time: u32) -> Vec<(u32, u32)> {
+    (0..=max_time)
+        .map(|held_time| (held_time, calc_distance(held_time, max_time)))
+        .collect()
+}
+
+pub(crate) fn get_winners(time_distances: &[(u32, u32)], distance_to_beat: u32) -> Vec<u32> {
     let mut winners: Vec<u32> = Vec::new();
-    for (time, distance) in time_distances {
+    for &(time, distance) in time_distances {
         if distance > distance_to_beat {
-            winners.push(*time);
+            winners.push(time);
         }
     }
-    return winners;
+    winners
 }
 
-fn calc_distance_u64(held_time: &u64, max_time: &u64) -> u64 {
-    return (max_time - held_time) * held_time;
+fn calc_distance_u64(held_time: u64, max_time: u64) -> u64 {
+    (max_time - held_time) * held_time
 }
 
-pub(crate) fn get_time_distances_u64(max_time: &u64) -> Vec<(u64, u64)> {
-    return (0..=*max_time)
-        .map(|held_time| (held_time, calc_distance_u64(&held_time, &max_time)))
-        .collect();
+pub(crate) fn get_time_distances_u64(max_time: u64) -> Vec<(u64, u64)> {
+    (0..=max_time)
+        .map(|held_time| (held_time, calc_distance_u64(held_time, max_time)))
+        .collect()
 }
 
-pub(crate) fn get_winners_u64(time_distances: &Vec<(u64, u64)>, distance_to_beat: &u64) -> Vec<u64> {
+pub(crate) fn get_winners_u64(time_distances: &[(u64, u64)], distance_to_beat: u64) -> Vec<u64> {
     let mut winners: Vec<u64> = Vec::new();
-    for (time, distance) in time_distances {
+    for &(time, distance) in time_distances {
         if distance > distance_to_beat {
-            winners.push(*time);
+            winners.push(time);
         }
     }
-    return winners;
+    winners
 }
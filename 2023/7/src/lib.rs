@@ -1,3 +1,27 @@
 pub mod part_1;
 pub mod part_2;
 pub mod utils;
+
+use common::errors::AocError;
+use common::input::Input;
+use common::solution::Solution;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = utils::Hands;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        utils::parse_input(input)
+    }
+
+    fn part_1(hands: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        part_1::solve(hands)
+    }
+
+    fn part_2(hands: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        part_2::solve(hands)
+    }
+}
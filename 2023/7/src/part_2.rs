@@ -1,11 +1,14 @@
 use common::errors::AocError;
 
-use crate::utils;
+use crate::utils::{Hand, Hands};
 
-pub fn solve(filename: &str) -> Result<u32, AocError> {
-    let hands = utils::get_hands(filename, true).unwrap();
+pub fn solve(hands: &Hands) -> Result<u32, AocError> {
+    let hands: Vec<(Hand, u32)> = hands
+        .iter()
+        .map(|&(hand, bid)| (hand.with_joker(), bid))
+        .collect();
 
-    let mut sorted_hands: Vec<&(utils::Hand, u32)> = hands.iter().collect();
-    sorted_hands.sort_by(|a, b| a.0.cmp(&b.0));
-    Ok(42)
+    let mut sorted_hands: Vec<&(Hand, u32)> = hands.iter().collect();
+    sorted_hands.sort_by_key(|(hand, _)| *hand);
+    Err(AocError::NotImplemented)
 }
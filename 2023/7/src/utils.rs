@@ -1,10 +1,11 @@
 use std::collections::HashMap;
+use std::str::FromStr;
 
-use common::{errors::AocError, input::InputReader};
+use common::{errors::AocError, input::Input, scan, trace};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
-pub(crate) enum Card {
+pub enum Card {
     Two = b'2',
     Three = b'3',
     Four = b'4',
@@ -22,8 +23,8 @@ pub(crate) enum Card {
 }
 
 impl Card {
-    fn index(&self) -> u8 {
-        match *self {
+    fn index(self) -> u8 {
+        match self {
             Card::Two => 1,
             Card::Three => 2,
             Card::Four => 3,
@@ -40,8 +41,10 @@ impl Card {
             Card::Unknown => 0,
         }
     }
-    fn index_joker(&self) -> u8 {
-        match *self {
+
+    #[allow(dead_code)]
+    fn index_joker(self) -> u8 {
+        match self {
             Card::Two => 1,
             Card::Three => 2,
             Card::Four => 3,
@@ -51,11 +54,10 @@ impl Card {
             Card::Eight => 7,
             Card::Nine => 8,
             Card::Ten => 9,
-            Card::Jack => 0,
             Card::Queen => 11,
             Card::King => 12,
             Card::Ace => 13,
-            Card::Unknown => 0,
+            Card::Jack | Card::Unknown => 0,
         }
     }
 }
@@ -89,12 +91,12 @@ impl Ord for Card {
 
 impl PartialOrd for Card {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum HandType {
+pub enum HandType {
     FiveOfAKind,
     FourOfAKind,
     FullHouse,
@@ -105,8 +107,8 @@ pub(crate) enum HandType {
 }
 
 impl HandType {
-    fn index(&self) -> u8 {
-        match *self {
+    fn index(self) -> u8 {
+        match self {
             HandType::FiveOfAKind => 7,
             HandType::FourOfAKind => 6,
             HandType::FullHouse => 5,
@@ -126,21 +128,21 @@ impl Ord for HandType {
 
 impl PartialOrd for HandType {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) struct Hand {
+pub struct Hand {
     cards: [Card; 5],
     joker: bool,
 }
 
 impl Hand {
-    pub(crate) fn hand_type(&self) -> HandType {
+    pub(crate) fn hand_type(self) -> HandType {
         let mut counts: HashMap<Card, usize> = HashMap::new();
-        for &card in self.cards.iter() {
-            *counts.entry(card).or_insert(0) += 1
+        for &card in &self.cards {
+            *counts.entry(card).or_insert(0) += 1;
         }
 
         let mut sorted_counts: Vec<(&Card, &usize)> = counts.iter().collect();
@@ -154,11 +156,11 @@ impl Hand {
         };
 
         if self.joker && self.cards.contains(&Card::Jack) {
-            println!("found joker on hand {:#?}", self.cards);
+            trace!("found joker on hand {:?}", self.cards);
             // fuck it we brute force
         }
 
-        return match *max {
+        match *max {
             5 => HandType::FiveOfAKind,
             4 => HandType::FourOfAKind,
             3 if *second_max == 2 => HandType::FullHouse,
@@ -166,17 +168,21 @@ impl Hand {
             2 if *second_max == 2 => HandType::TwoPair,
             2 => HandType::OnePair,
             _ => HandType::HighCard,
-        };
+        }
     }
 
-    fn calc_joker_hand(&self) {}
+    pub(crate) fn with_joker(self) -> Self {
+        Self {
+            joker: true,
+            ..self
+        }
+    }
 }
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         if self.hand_type() == other.hand_type() {
-            let card_it = self.cards.iter().clone().enumerate();
-            for (i, card) in card_it {
+            for (i, card) in self.cards.iter().enumerate() {
                 if *card != other.cards[i] {
                     return card.cmp(&other.cards[i]);
                 }
@@ -190,27 +196,28 @@ impl Ord for Hand {
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
-pub(crate) fn get_hands(filename: &str, joker: bool) -> Result<Vec<(Hand, u32)>, AocError> {
-    let lines = InputReader::as_lines(&filename)?;
-    let hands: Vec<(Hand, u32)> = lines
-        .iter()
-        .map(|line| parse_line(line, joker).unwrap())
-        .collect();
-    Ok(hands)
+impl FromStr for Hand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards: Vec<Card> = s.chars().map(Card::from).collect();
+        let count = cards.len();
+        let cards = cards
+            .try_into()
+            .map_err(|_| format!("expected 5 cards, found {count}"))?;
+        Ok(Self {
+            cards,
+            joker: false,
+        })
+    }
 }
 
-fn parse_line(line: &str, joker: bool) -> Result<(Hand, u32), AocError> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    let cards: Vec<Card> = parts[0].chars().map(|char| Card::from(char)).collect();
-    let hand = Hand {
-        cards: cards.try_into().unwrap(),
-        joker,
-    };
-    let bid: u32 = parts[1].parse().unwrap();
+pub type Hands = Vec<(Hand, u32)>;
 
-    Ok((hand, bid))
+pub fn parse_input(input: &Input) -> Result<Hands, AocError> {
+    Ok(scan::lines("{} {}", input.as_string())?)
 }
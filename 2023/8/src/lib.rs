@@ -1,3 +1,27 @@
 pub mod part_1;
 pub mod part_2;
 pub mod utils;
+
+use common::errors::AocError;
+use common::input::Input;
+use common::solution::Solution;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = utils::Network;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        utils::parse_input(input)
+    }
+
+    fn part_1(network: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        part_1::solve(network)
+    }
+
+    fn part_2(network: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        part_2::solve(network)
+    }
+}
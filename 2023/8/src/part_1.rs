@@ -1,8 +1,7 @@
 use common::errors::AocError;
 
-use crate::utils;
+use crate::utils::{self, Network};
 
-pub fn solve(filename: &str) -> Result<u64, AocError> {
-    let (instructions, graph) = utils::parse_input(filename)?;
-    Ok(utils::get_n_steps(instructions, graph, "AAA", "ZZZ").into())
+pub fn solve(network: &Network) -> Result<u64, AocError> {
+    Ok(utils::get_n_steps(network, "AAA", "ZZZ")?.into())
 }
@@ -1,8 +1,7 @@
 use common::errors::AocError;
 
-use crate::utils;
+use crate::utils::{self, Network};
 
-pub fn solve(filename: &str) -> Result<u64, AocError> {
-    let (instructions, graph) = utils::parse_input(filename)?;
-    Ok(utils::get_n_steps_2_spooky_boogaloo(instructions, graph))
+pub fn solve(network: &Network) -> Result<u64, AocError> {
+    Ok(utils::get_n_steps_2_spooky_boogaloo(network))
 }
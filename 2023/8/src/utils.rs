@@ -1,48 +1,47 @@
 use std::collections::HashMap;
 
-use common::{errors::AocError, input::InputReader};
+use common::{cancel, errors::AocError, input::Input, scan, trace};
 
-pub(crate) fn parse_input(
-    filename: &str,
-) -> Result<(Vec<char>, HashMap<String, (String, String)>), AocError> {
-    let lines = InputReader::as_paragraphs(&filename)?;
-
-    let instructions: Vec<char> = lines[0].chars().collect();
+pub struct Network {
+    pub instructions: Vec<char>,
+    pub graph: HashMap<String, (String, String)>,
+}
 
-    let graph_parts: Vec<(String, (String, String))> = lines[1]
-        .split('\n')
-        .map(|line| {
-            let (elements, left_right): (&str, &str) = line.split_once(" = ").unwrap();
+pub fn parse_input(input: &Input) -> Result<Network, AocError> {
+    let paragraphs = input.as_paragraphs()?;
+    let [instructions, network, ..] = &paragraphs[..] else {
+        return Err(AocError::from("missing network section"));
+    };
 
-            let binding = left_right.replace('(', "").replace(')', "");
-            let (left, right): (&str, &str) = binding.split_once(", ").unwrap();
+    let instructions: Vec<char> = instructions.chars().collect();
 
-            return (elements.into(), (left.into(), right.into()));
-        })
-        .collect();
-    let graph: HashMap<String, (String, String)> = graph_parts
-        .into_iter()
-        .map(|(key, value)| (key, value))
-        .collect();
+    let graph: HashMap<String, (String, String)> =
+        scan::lines::<(String, String, String)>("{} = ({}, {})", network)?
+            .into_iter()
+            .map(|(node, left, right)| (node, (left, right)))
+            .collect();
 
-    Ok((instructions, graph))
+    Ok(Network {
+        instructions,
+        graph,
+    })
 }
 
-pub(crate) fn get_n_steps(
-    instructions: Vec<char>,
-    graph: HashMap<String, (String, String)>,
-    from: &str,
-    to: &str,
-) -> u32 {
+pub(crate) fn get_n_steps(network: &Network, from: &str, to: &str) -> Result<u32, AocError> {
+    let Network {
+        instructions,
+        graph,
+    } = network;
+
     let mut n_steps = 0;
     let mut index = 0;
     let n_instructions = instructions.len();
 
     let mut curr_node = from;
     loop {
-        println!("steps: {n_steps}");
-        println!("index: {index}/{n_instructions}");
-        println!("curr: {curr_node}");
+        // The walk never ends if `to` cannot be reached, so let a timeout stop it
+        cancel::check()?;
+        trace!("step {n_steps}: at {curr_node}, instruction {index}/{n_instructions}");
 
         if curr_node == to {
             break;
@@ -54,30 +53,28 @@ pub(crate) fn get_n_steps(
 
         let next_nodes = graph.get(curr_node).unwrap();
         let next_instruction = instructions[index];
-        println!("next_nodes: {next_nodes:?}");
+        trace!("next_nodes: {next_nodes:?}");
 
         curr_node = if next_instruction == 'L' {
-            println!("moving {} to {}", next_instruction, next_nodes.0);
             &next_nodes.0
         } else {
-            println!("moving {} to {}", next_instruction, next_nodes.1);
             &next_nodes.1
         };
-        println!("now at {curr_node}");
+        trace!("moving {next_instruction} to {curr_node}");
 
         index += 1;
         n_steps += 1;
-        println!("")
     }
 
-    return n_steps;
+    Ok(n_steps)
 }
 
-fn get_n_steps_to_z(
-    instructions: &Vec<char>,
-    graph: &HashMap<String, (String, String)>,
-    from: &str,
-) -> u64 {
+fn get_n_steps_to_z(network: &Network, from: &str) -> u64 {
+    let Network {
+        instructions,
+        graph,
+    } = network;
+
     let mut n_steps = 0;
     let mut index = 0;
     let n_instructions = instructions.len();
@@ -105,8 +102,9 @@ fn get_n_steps_to_z(
         n_steps += 1;
     }
 
-    return n_steps;
+    n_steps
 }
+
 fn gcd(a: u64, b: u64) -> u64 {
     if b == 0 { a } else { gcd(b, a % b) }
 }
@@ -119,15 +117,13 @@ fn lcm_of_vec(numbers: &[u64]) -> u64 {
     numbers.iter().copied().reduce(lcm).unwrap()
 }
 
-pub(crate) fn get_n_steps_2_spooky_boogaloo(
-    instructions: Vec<char>,
-    graph: HashMap<String, (String, String)>,
-) -> u64 {
-    let mut curr_nodes: Vec<&String> = graph.keys().filter(|k| k.ends_with('A')).collect();
-    let cycle_lengths: Vec<u64> = curr_nodes
-        .iter()
-        .map(|start| get_n_steps_to_z(&instructions, &graph, start))
+pub(crate) fn get_n_steps_2_spooky_boogaloo(network: &Network) -> u64 {
+    let cycle_lengths: Vec<u64> = network
+        .graph
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .map(|start| get_n_steps_to_z(network, start))
         .collect();
 
-    return lcm_of_vec(&cycle_lengths);
+    lcm_of_vec(&cycle_lengths)
 }
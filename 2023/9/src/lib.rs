@@ -1,3 +1,41 @@
 pub mod part_1;
 pub mod part_2;
 pub mod utils;
+
+use common::errors::AocError;
+use common::input::Input;
+use common::solution::Solution;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        utils::parse_input(input)
+    }
+
+    fn part_1(all_series: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        part_1::solve(all_series)
+    }
+
+    fn part_2(all_series: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        part_2::solve(all_series)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+
+    #[test]
+    fn test_sample() {
+        let all_series = Day9::parse(&Input::new(SAMPLE).unwrap()).unwrap();
+        assert_eq!(Day9::part_1(&all_series).unwrap(), 114);
+        assert_eq!(Day9::part_2(&all_series).unwrap(), 2);
+    }
+}
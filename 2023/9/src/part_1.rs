@@ -2,12 +2,6 @@ use common::errors::AocError;
 
 use crate::utils;
 
-pub fn solve(filename: &str) -> Result<i64, AocError> {
-    let all_series = utils::parse_input(filename)?;
-    return all_series
-        .iter()
-        .fold(Ok(0), |acc: Result<i64, AocError>, e| {
-            let forecast = utils::forecast(e);
-            Ok(acc.unwrap() + forecast)
-        });
+pub fn solve(all_series: &[Vec<i64>]) -> Result<i64, AocError> {
+    Ok(all_series.iter().map(|e| utils::forecast(e)).sum())
 }
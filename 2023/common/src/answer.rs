@@ -0,0 +1,331 @@
+use crate::grid::Grid;
+use std::fmt::{self, Display, Formatter};
+
+/// Height of a letter in the Advent of Code block-letter font
+const LETTER_HEIGHT: usize = 6;
+
+/// The Advent of Code block-letter font, each letter trimmed of blank columns
+const FONT: [(char, [&str; LETTER_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/**
+The answer to one part of a puzzle
+
+Solvers can return any integer type, a string, or a grid of characters, and
+the runner turns it into an `Answer` to display and check it. Integers are
+widened to 128 bits, so no answer has to be narrowed to fit.
+
+A grid drawn in the Advent of Code block-letter font displays as the letters
+it spells, which is what the puzzle expects to be submitted. Other grids
+display as their rows.
+
+Answers compare by value, that is by what they display as, numbers aside:
+`Signed(42)` equals `Unsigned(42)`, and a grid equals the text of the letters
+it spells, as well as any other grid spelling them. Known answers read from a file are
+turned into an `Answer` with [`Answer::parse`].
+
+# Examples
+
+```
+use common::answer::Answer;
+
+assert_eq!(Answer::from(42_u32), Answer::from(42_i64));
+assert_eq!(Answer::from(u128::MAX).to_string(), "340282366920938463463374607431768211455");
+
+let grid = Answer::grid([
+    "#..#.###.",
+    "#..#..#..",
+    "####..#..",
+    "#..#..#..",
+    "#..#..#..",
+    "#..#.###.",
+]);
+assert_eq!(grid.to_string(), "HI");
+assert_eq!(grid, Answer::parse("HI"));
+```
+*/
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    Text(String),
+    /// Rows of characters, top to bottom
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Builds a grid answer from its rows
+    pub fn grid<R: Into<String>>(rows: impl IntoIterator<Item = R>) -> Self {
+        Answer::Grid(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Reads an answer written as text, such as a known answer, as a number if it is one
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        text.parse()
+            .map(Answer::Unsigned)
+            .or_else(|_| text.parse().map(Answer::Signed))
+            .unwrap_or_else(|_| Answer::Text(text.to_string()))
+    }
+
+    /// The letters spelled by a grid answer, if it is one written in the block-letter font
+    #[must_use]
+    pub fn letters(&self) -> Option<String> {
+        match self {
+            Answer::Grid(rows) => recognize_letters(rows),
+            _ => None,
+        }
+    }
+
+    /// Whether the answer needs several lines to be displayed
+    #[must_use]
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Answer::Grid(rows) => rows.len() > 1 && self.letters().is_none(),
+            Answer::Text(text) => text.contains('\n'),
+            Answer::Unsigned(_) | Answer::Signed(_) => false,
+        }
+    }
+
+    /// The answer as a signed integer, if it is a number that fits
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Unsigned(n) => i128::try_from(*n).ok(),
+            Answer::Signed(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Unsigned(_) | Answer::Signed(_), Answer::Unsigned(_) | Answer::Signed(_)) => {
+                self.as_i128().is_some() && self.as_i128() == other.as_i128()
+            }
+            // Grids included, so that two grids spelling the same letters are
+            // equal, like each of them is to the text of those letters
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Grid(rows) => match recognize_letters(rows) {
+                Some(letters) => f.write_str(&letters),
+                None => f.write_str(&rows.join("\n")),
+            },
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($variant:ident: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::$variant(value.try_into().expect("integers fit in 128 bits"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(Unsigned: u8, u16, u32, u64, u128, usize);
+impl_from_integer!(Signed: i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<Grid<char>> for Answer {
+    fn from(grid: Grid<char>) -> Self {
+        Answer::grid(grid.rows().map(|row| row.iter().collect::<String>()))
+    }
+}
+
+impl From<Vec<Vec<char>>> for Answer {
+    fn from(rows: Vec<Vec<char>>) -> Self {
+        Answer::grid(
+            rows.into_iter()
+                .map(|row| row.into_iter().collect::<String>()),
+        )
+    }
+}
+
+/**
+Reads the letters drawn in the Advent of Code block-letter font
+
+`#` (or `█`) is a lit cell and anything else is blank. The grid must be six
+rows high, blank rows around it aside, with letters separated by at least one
+blank column. Returns `None` if there is no letter or one isn't recognized.
+
+# Examples
+
+```
+use common::answer::recognize_letters;
+
+let rows = [
+    "####.#..#",
+    "#....#.#.",
+    "###..##..",
+    "#....#.#.",
+    "#....#.#.",
+    "####.#..#",
+];
+assert_eq!(recognize_letters(&rows), Some("EK".to_string()));
+```
+*/
+#[must_use]
+pub fn recognize_letters<R: AsRef<str>>(rows: &[R]) -> Option<String> {
+    let lit = |c: char| c == '#' || c == '█';
+    let mut grid: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.as_ref().chars().map(lit).collect())
+        .collect();
+    let is_blank = |row: &Vec<bool>| !row.contains(&true);
+    while grid.last().is_some_and(is_blank) {
+        grid.pop();
+    }
+    let top = grid.iter().take_while(|row| is_blank(row)).count();
+    grid.drain(..top);
+    if grid.len() != LETTER_HEIGHT {
+        return None;
+    }
+
+    let width = grid.iter().map(Vec::len).max()?;
+    let column_is_blank = |col: usize| {
+        grid.iter()
+            .all(|row| !row.get(col).copied().unwrap_or(false))
+    };
+
+    let mut letters = String::new();
+    let mut col = 0;
+    while col < width {
+        if column_is_blank(col) {
+            col += 1;
+            continue;
+        }
+
+        let start = col;
+        while col < width && !column_is_blank(col) {
+            col += 1;
+        }
+        let glyph: Vec<String> = grid
+            .iter()
+            .map(|row| {
+                (start..col)
+                    .map(|c| {
+                        if row.get(c).copied().unwrap_or(false) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (letter, _) = FONT.iter().find(|(_, pattern)| glyph == pattern)?;
+        letters.push(*letter);
+    }
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `text` in the block-letter font, letters separated by a blank column
+    fn draw(text: &str) -> Vec<String> {
+        (0..LETTER_HEIGHT)
+            .map(|row| {
+                let mut line = String::new();
+                for c in text.chars() {
+                    let (_, pattern) = FONT.iter().find(|(letter, _)| *letter == c).unwrap();
+                    line.push_str(pattern[row]);
+                    line.push('.');
+                }
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recognizes_the_whole_font() {
+        let alphabet: String = FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(recognize_letters(&draw(&alphabet)), Some(alphabet));
+    }
+
+    #[test]
+    fn test_unknown_or_malformed_grids() {
+        let mut rows = draw("RZ");
+        rows[2].replace_range(0..1, ".");
+        assert_eq!(recognize_letters(&rows), None);
+        assert_eq!(recognize_letters(&draw("RZ")[..5]), None);
+        assert_eq!(recognize_letters(&["......"; 6]), None);
+
+        let grid = Answer::grid(["#.#", ".#."]);
+        assert!(grid.is_multiline());
+        assert_eq!(grid.to_string(), "#.#\n.#.");
+    }
+
+    #[test]
+    fn test_compares_by_value() {
+        assert_eq!(Answer::from(7_u8), Answer::parse("7"));
+        assert_eq!(Answer::from(-3_i32), Answer::parse("-3"));
+        assert_ne!(Answer::from(-3_i32), Answer::parse("3"));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::parse(&u128::MAX.to_string())
+        );
+        assert_ne!(Answer::from(u128::MAX), Answer::from(-1_i8));
+        assert_eq!(Answer::from("qzvxkl"), Answer::parse("qzvxkl"));
+        assert_eq!(Answer::grid(draw("PZGPKPEB")), Answer::parse("PZGPKPEB"));
+
+        // Equality stays transitive for grids drawn differently
+        let mut wide = draw("HI");
+        for row in &mut wide {
+            row.insert(0, '.');
+        }
+        assert_ne!(wide, draw("HI"));
+        assert_eq!(Answer::grid(wide), Answer::grid(draw("HI")));
+        assert_ne!(Answer::grid(["#.", ".#"]), Answer::grid(["#.", "##"]));
+    }
+}
@@ -0,0 +1,307 @@
+use crate::answer::Answer;
+use crate::errors::InputError;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the file, next to the inputs, that lists the known answers
+pub const ANSWERS_FILE: &str = "answers";
+
+/// Extension of the sidecar file holding the known answers of a single input
+pub const EXPECTED_EXTENSION: &str = "expected";
+
+/**
+The known answers of a day, per input file and part
+
+The answers file has one `[section]` per input file (`input`, `sample_1`, ...),
+each holding `part = answer` lines. Blank lines and lines starting with `#`
+are ignored:
+
+```text
+# Day 8
+[input]
+1 = 20659
+2 = 15690466351717
+
+[sample_3]
+2 = 6
+```
+
+An input can also have its known answers in a sidecar file named after it,
+such as `sample_3.expected`, holding `part = answer` lines without a section.
+Answers in a sidecar take precedence over the answers file.
+
+# Examples
+
+```
+use common::answer::Answer;
+use common::answers::{Answers, Verdict};
+
+let answers = Answers::parse("[sample_1]\n1 = 4361\n").unwrap();
+assert_eq!(answers.check("sample_1", 1, &Answer::from(4361_u32)), Verdict::Match);
+assert_eq!(answers.check("sample_1", 2, &Answer::from(467_835_u32)), Verdict::Unknown);
+```
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    expected: HashMap<(String, u8), String>,
+}
+
+/// How an answer compares to the known answer for its input and part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer is the known answer
+    Match,
+    /// The answer differs from the known answer
+    Mismatch { expected: String },
+    /// No answer is known for this input and part
+    Unknown,
+}
+
+impl Verdict {
+    /// Short lowercase name, for machine-readable reports
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Match => "match",
+            Verdict::Mismatch { .. } => "mismatch",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Answers {
+    /**
+    Parses the contents of an answers file
+
+    # Errors
+    Returns `InputError::InvalidFormat`, naming the line, if a line is neither
+    a section header, a `part = answer` pair, a comment nor blank, or if an
+    answer appears before the first section
+    */
+    pub fn parse(content: &str) -> Result<Self, InputError> {
+        Self::parse_in(content, None)
+    }
+
+    /**
+    Parses the contents of the sidecar file of the input named `section`
+
+    # Errors
+    Returns `InputError::InvalidFormat`, naming the line, if a line is not
+    valid in an answers file
+    */
+    pub fn parse_sidecar(section: &str, content: &str) -> Result<Self, InputError> {
+        Self::parse_in(content, Some(section.to_string()))
+    }
+
+    /// Parses answers that belong to `section` until a section header says otherwise
+    fn parse_in(content: &str, mut section: Option<String>) -> Result<Self, InputError> {
+        let mut expected = HashMap::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            let invalid = |msg: &str| {
+                InputError::InvalidFormat(format!("{ANSWERS_FILE} line {}: {msg}", i + 1))
+            };
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(name.trim().to_string());
+                continue;
+            }
+
+            let (part, answer) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected '[input]' or '<part> = <answer>'"))?;
+            let part: u8 = match part.trim() {
+                "1" => 1,
+                "2" => 2,
+                other => return Err(invalid(&format!("invalid part '{other}'"))),
+            };
+            let input = section
+                .clone()
+                .ok_or_else(|| invalid("answer given before any [input] section"))?;
+
+            expected.insert((input, part), answer.trim().to_string());
+        }
+
+        Ok(Self { expected })
+    }
+
+    /**
+    Loads the answers file at `path`
+
+    A missing file is not an error: it simply means no answer is known yet.
+
+    # Errors
+    Returns an error if the file exists but cannot be read or parsed
+    */
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, InputError> {
+        match read_if_exists(path.as_ref())? {
+            Some(content) => Self::parse(&content),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /**
+    Loads the known answers of the given input file, from the answers file next
+    to it and from its sidecar file
+
+    # Errors
+    Returns an error if either file exists but cannot be read or parsed
+    */
+    pub fn load_for(input: impl AsRef<Path>) -> Result<Self, InputError> {
+        let input = input.as_ref();
+        let mut answers = Self::load(Self::path_for(input))?;
+        if let Some(content) = read_if_exists(&Self::sidecar_for(input))? {
+            let sidecar = Self::parse_sidecar(&Self::section_for(input), &content)?;
+            answers.expected.extend(sidecar.expected);
+        }
+        Ok(answers)
+    }
+
+    /// The answers file that belongs next to the given input file
+    #[must_use]
+    pub fn path_for(input: impl AsRef<Path>) -> PathBuf {
+        input
+            .as_ref()
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(ANSWERS_FILE)
+    }
+
+    /// The sidecar file of an input file, such as `sample_1.expected`
+    #[must_use]
+    pub fn sidecar_for(input: impl AsRef<Path>) -> PathBuf {
+        let mut sidecar = input.as_ref().as_os_str().to_owned();
+        sidecar.push(".");
+        sidecar.push(EXPECTED_EXTENSION);
+        PathBuf::from(sidecar)
+    }
+
+    /// The section name used for an input file: its file name
+    #[must_use]
+    pub fn section_for(input: impl AsRef<Path>) -> String {
+        let input = input.as_ref();
+        input
+            .file_name()
+            .map_or_else(|| input.to_string_lossy(), |name| name.to_string_lossy())
+            .into_owned()
+    }
+
+    /// The known answer for `input` and `part`, if any
+    #[must_use]
+    pub fn expected(&self, input: &str, part: u8) -> Option<&str> {
+        self.expected
+            .get(&(input.to_string(), part))
+            .map(String::as_str)
+    }
+
+    /// The parts of `input` that have a known answer, in order
+    #[must_use]
+    pub fn known_parts(&self, input: &str) -> Vec<u8> {
+        [1, 2]
+            .into_iter()
+            .filter(|&part| self.expected(input, part).is_some())
+            .collect()
+    }
+
+    /**
+    Compares `answer` with the known answer for `input` and `part`
+
+    The known answer is read with [`Answer::parse`], so numbers compare by value
+    and a grid of block letters matches the letters it spells.
+    */
+    #[must_use]
+    pub fn check(&self, input: &str, part: u8, answer: &Answer) -> Verdict {
+        match self.expected(input, part) {
+            Some(expected) if Answer::parse(expected) == *answer => Verdict::Match,
+            Some(expected) => Verdict::Mismatch {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// Reads the file at `path`, or `None` if there is no such file
+fn read_if_exists(path: &Path) -> Result<Option<String>, InputError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sections_and_comments() {
+        let answers =
+            Answers::parse("# Day 9\n[input]\n1 = 1479011877\n2=973\n\n[sample_1]\n  2 = -3  \n")
+                .unwrap();
+
+        assert_eq!(answers.expected("input", 1), Some("1479011877"));
+        assert_eq!(answers.expected("input", 2), Some("973"));
+        assert_eq!(answers.expected("sample_1", 2), Some("-3"));
+        assert_eq!(answers.expected("sample_1", 1), None);
+    }
+
+    #[test]
+    fn test_check_verdicts() {
+        let answers = Answers::parse("[input]\n1 = 42\n2 = -7\n").unwrap();
+
+        assert_eq!(
+            answers.check("input", 1, &Answer::from(42_u8)),
+            Verdict::Match
+        );
+        assert_eq!(
+            answers.check("input", 1, &Answer::from(41_u8)),
+            Verdict::Mismatch {
+                expected: "42".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check("input", 2, &Answer::from(-7_i64)),
+            Verdict::Match
+        );
+        assert_eq!(
+            answers.check("sample_1", 2, &Answer::from(41_u8)),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let err = Answers::parse("1 = 42\n").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+
+        let err = Answers::parse("[input]\n3 = 42\n").unwrap_err();
+        assert!(err.to_string().contains("line 2: invalid part '3'"));
+    }
+
+    #[test]
+    fn test_paths_next_to_input() {
+        assert_eq!(Answers::path_for("8/sample_1"), Path::new("8/answers"));
+        assert_eq!(Answers::path_for("input"), Path::new("answers"));
+        assert_eq!(Answers::section_for("8/sample_1"), "sample_1");
+        assert_eq!(
+            Answers::sidecar_for("8/sample_1"),
+            Path::new("8/sample_1.expected")
+        );
+    }
+
+    #[test]
+    fn test_sidecar_answers_belong_to_their_input() {
+        let answers = Answers::parse_sidecar("sample_3", "# part 2 only\n2 = 6\n").unwrap();
+        assert_eq!(answers.expected("sample_3", 2), Some("6"));
+        assert_eq!(answers.known_parts("sample_3"), [2]);
+        assert!(answers.known_parts("sample_1").is_empty());
+    }
+}
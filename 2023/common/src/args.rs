@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+
+/// A `--flag` accepted by a command, with an optional short form and value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flag {
+    pub long: &'static str,
+    pub short: Option<char>,
+    /// Name of the flag's value, shown as `<NAME>` in the help; `None` for a switch
+    pub value_name: Option<&'static str>,
+    pub help: &'static str,
+}
+
+impl Flag {
+    /// A flag without a value, such as `--time`
+    #[must_use]
+    pub const fn switch(long: &'static str, help: &'static str) -> Self {
+        Self {
+            long,
+            short: None,
+            value_name: None,
+            help,
+        }
+    }
+
+    /// A flag followed by a value, such as `--part 2` or `--part=2`
+    #[must_use]
+    pub const fn with_value(
+        long: &'static str,
+        value_name: &'static str,
+        help: &'static str,
+    ) -> Self {
+        Self {
+            long,
+            short: None,
+            value_name: Some(value_name),
+            help,
+        }
+    }
+
+    /// Also accepts the flag as `-c`
+    #[must_use]
+    pub const fn short(self, short: char) -> Self {
+        Self {
+            short: Some(short),
+            ..self
+        }
+    }
+
+    /// How the flag is written in the help, such as `-p, --part <PART>`
+    fn signature(&self) -> String {
+        let mut signature = match self.short {
+            Some(short) => format!("-{short}, --{}", self.long),
+            None => format!("    --{}", self.long),
+        };
+        if let Some(name) = self.value_name {
+            let _ = write!(signature, " <{name}>");
+        }
+        signature
+    }
+}
+
+/// An argument given by position rather than by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Positional {
+    pub name: &'static str,
+    pub help: &'static str,
+}
+
+/// Why the command line could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsError {
+    /// A flag that the command doesn't accept
+    UnknownFlag(String),
+    /// A flag that takes a value came last
+    MissingValue(&'static str),
+    /// A flag's value, or a positional argument, is not one the command accepts
+    InvalidValue {
+        name: String,
+        value: String,
+        expected: String,
+    },
+    /// More positional arguments than the command accepts
+    UnexpectedArgument(String),
+    /// A switch given a value, as in `--time=yes`
+    UnexpectedValue(&'static str),
+    /// Two arguments that cannot be used together
+    Conflict(String, String),
+    /// Any other misuse, described by the message
+    Invalid(String),
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgsError::UnknownFlag(flag) => write!(f, "Unknown flag '{flag}'"),
+            ArgsError::MissingValue(flag) => write!(f, "--{flag} expects a value"),
+            ArgsError::InvalidValue {
+                name,
+                value,
+                expected,
+            } => write!(f, "Invalid {name} '{value}'. Use {expected}"),
+            ArgsError::UnexpectedArgument(arg) => write!(f, "Unexpected argument '{arg}'"),
+            ArgsError::UnexpectedValue(flag) => write!(f, "--{flag} does not take a value"),
+            ArgsError::Conflict(a, b) => write!(f, "{a} cannot be used with {b}"),
+            ArgsError::Invalid(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl Error for ArgsError {}
+
+/**
+What a command accepts, from which its arguments are parsed and its help written
+
+Every command accepts `-h, --help` and `-V, --version`. Parsing stops at
+either of them, so the caller can print the help or version even when the
+rest of the command line is wrong.
+
+# Examples
+
+```
+use common::args::{Flag, Spec};
+
+let spec = Spec::new("aoc-2023-8", "Solves a puzzle")
+    .positional("PART", "1, 2 or both")
+    .flag(Flag::with_value("input", "FILE", "Input file").short('i'))
+    .flag(Flag::switch("verbose", "Show more").short('v'));
+
+let args = ["both", "-vv", "--input=sample_2"].map(String::from);
+let matches = spec.parse(&args).unwrap();
+assert_eq!(matches.positional(0), Some("both"));
+assert_eq!(matches.value("input"), Some("sample_2"));
+assert_eq!(matches.count("verbose"), 2);
+
+assert!(spec.parse(&["--inptu".to_string()]).is_err());
+assert!(spec.help().contains("-i, --input <FILE>"));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Spec {
+    name: String,
+    about: &'static str,
+    positionals: Vec<Positional>,
+    flags: Vec<Flag>,
+}
+
+impl Spec {
+    /// Flag that asks for the help
+    pub const HELP: Flag = Flag::switch("help", "Print this help").short('h');
+
+    /// Flag that asks for the version
+    pub const VERSION: Flag = Flag::switch("version", "Print the version").short('V');
+
+    /// A command called `name`, described in the help by `about`
+    #[must_use]
+    pub fn new(name: impl Into<String>, about: &'static str) -> Self {
+        Self {
+            name: name.into(),
+            about,
+            positionals: Vec::new(),
+            flags: vec![Self::HELP, Self::VERSION],
+        }
+    }
+
+    /// Accepts one more positional argument, after the previous ones
+    #[must_use]
+    pub fn positional(mut self, name: &'static str, help: &'static str) -> Self {
+        self.positionals.push(Positional { name, help });
+        self
+    }
+
+    /// Accepts one more flag
+    #[must_use]
+    pub fn flag(mut self, flag: Flag) -> Self {
+        debug_assert!(
+            self.find_long(flag.long).is_none(),
+            "--{} declared twice",
+            flag.long
+        );
+        self.flags.push(flag);
+        self
+    }
+
+    /// The name of the command, as shown in the help
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn find_long(&self, long: &str) -> Option<&Flag> {
+        self.flags.iter().find(|flag| flag.long == long)
+    }
+
+    fn find_short(&self, short: char) -> Option<&Flag> {
+        self.flags.iter().find(|flag| flag.short == Some(short))
+    }
+
+    /**
+    Parses the arguments, without the program name
+
+    Flags can come anywhere, in the forms `--flag value`, `--flag=value`,
+    `-f value` and `-fvalue`. Short switches can be grouped, as in `-vv`. A
+    lone `-` is a positional argument, and everything after `--` is too.
+
+    # Errors
+    Returns an error for an unknown flag, a missing value or too many
+    positional arguments
+    */
+    pub fn parse(&self, args: &[String]) -> Result<Matches, ArgsError> {
+        let mut matches = Matches::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                for arg in args.by_ref() {
+                    self.push_positional(&mut matches, arg)?;
+                }
+                break;
+            }
+
+            // The flags given by this argument, with their values
+            let mut given: Vec<(&Flag, String)> = Vec::new();
+
+            if let Some(long) = arg.strip_prefix("--") {
+                let (name, inline) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (long, None),
+                };
+                let flag = self
+                    .find_long(name)
+                    .ok_or_else(|| ArgsError::UnknownFlag(arg.clone()))?;
+                let value = match (flag.value_name, inline) {
+                    (None, Some(_)) => return Err(ArgsError::UnexpectedValue(flag.long)),
+                    (None, None) => String::new(),
+                    (Some(_), Some(value)) => value,
+                    (Some(_), None) => args
+                        .next()
+                        .ok_or(ArgsError::MissingValue(flag.long))?
+                        .clone(),
+                };
+                given.push((flag, value));
+            } else if let Some(shorts) = arg.strip_prefix('-').filter(|s| !s.is_empty()) {
+                for (i, short) in shorts.char_indices() {
+                    let flag = self
+                        .find_short(short)
+                        .ok_or_else(|| ArgsError::UnknownFlag(format!("-{short}")))?;
+                    if flag.value_name.is_none() {
+                        given.push((flag, String::new()));
+                        continue;
+                    }
+
+                    // The rest of the group, or else the next argument, is the value
+                    let rest = &shorts[i + short.len_utf8()..];
+                    let value = if rest.is_empty() {
+                        args.next()
+                            .ok_or(ArgsError::MissingValue(flag.long))?
+                            .clone()
+                    } else {
+                        rest.to_string()
+                    };
+                    given.push((flag, value));
+                    break;
+                }
+            } else {
+                self.push_positional(&mut matches, arg)?;
+            }
+
+            let stop = given
+                .iter()
+                .any(|(flag, _)| [Self::HELP, Self::VERSION].contains(flag));
+            for (flag, value) in given {
+                matches.record(flag.long, value);
+            }
+            if stop {
+                return Ok(matches);
+            }
+        }
+
+        Ok(matches)
+    }
+
+    fn push_positional(&self, matches: &mut Matches, arg: &str) -> Result<(), ArgsError> {
+        if matches.positionals.len() == self.positionals.len() {
+            return Err(ArgsError::UnexpectedArgument(arg.to_string()));
+        }
+        matches.positionals.push(arg.to_string());
+        Ok(())
+    }
+
+    /// The one-line usage summary
+    #[must_use]
+    pub fn usage(&self) -> String {
+        let mut usage = format!("Usage: {} [OPTIONS]", self.name);
+        for positional in &self.positionals {
+            let _ = write!(usage, " [{}]", positional.name);
+        }
+        usage
+    }
+
+    /// The full help: description, usage, then each argument and flag
+    #[must_use]
+    pub fn help(&self) -> String {
+        let mut help = format!("{}\n\n{}\n", self.about, self.usage());
+
+        let column = self
+            .flags
+            .iter()
+            .map(|flag| flag.signature().len())
+            .chain(self.positionals.iter().map(|p| p.name.len() + 2))
+            .max()
+            .unwrap_or(0);
+
+        if !self.positionals.is_empty() {
+            help.push_str("\nArguments:\n");
+            for positional in &self.positionals {
+                let name = format!("[{}]", positional.name);
+                let _ = writeln!(help, "  {name:<column$}  {}", positional.help);
+            }
+        }
+
+        help.push_str("\nOptions:\n");
+        for flag in &self.flags {
+            let _ = writeln!(help, "  {:<column$}  {}", flag.signature(), flag.help);
+        }
+        help
+    }
+}
+
+/// The arguments found by [`Spec::parse`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Matches {
+    /// Values given to each flag, by long name; switches get empty strings
+    flags: HashMap<&'static str, Vec<String>>,
+    positionals: Vec<String>,
+}
+
+impl Matches {
+    fn record(&mut self, long: &'static str, value: String) {
+        self.flags.entry(long).or_default().push(value);
+    }
+
+    /// Whether the flag was given at all
+    #[must_use]
+    pub fn is_present(&self, long: &str) -> bool {
+        self.flags.contains_key(long)
+    }
+
+    /// How many times the flag was given, as with `-vvv`
+    #[must_use]
+    pub fn count(&self, long: &str) -> usize {
+        self.flags.get(long).map_or(0, Vec::len)
+    }
+
+    /// The value of the flag, the last one if it was given more than once
+    #[must_use]
+    pub fn value(&self, long: &str) -> Option<&str> {
+        self.flags.get(long)?.last().map(String::as_str)
+    }
+
+    /// The positional argument at `index`, if it was given
+    #[must_use]
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positionals.get(index).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Spec {
+        Spec::new("aoc", "Test command")
+            .positional("PART", "Part")
+            .positional("INPUT", "Input")
+            .flag(Flag::with_value("sample", "N", "Sample").short('s'))
+            .flag(Flag::switch("time", "Time"))
+            .flag(Flag::switch("verbose", "Verbose").short('v'))
+    }
+
+    fn parse(args: &[&str]) -> Result<Matches, ArgsError> {
+        spec().parse(&args.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_flag_forms() {
+        let matches = parse(&["-s2", "1", "--time", "-vsv", "-", "-v"]).unwrap();
+        assert_eq!(matches.value("sample"), Some("v"));
+        assert_eq!(matches.count("sample"), 2);
+        assert_eq!(matches.count("verbose"), 2);
+        assert!(matches.is_present("time"));
+        assert_eq!(matches.positional(0), Some("1"));
+        assert_eq!(matches.positional(1), Some("-"));
+
+        let matches = parse(&["--sample=3", "--", "--time"]).unwrap();
+        assert_eq!(matches.value("sample"), Some("3"));
+        assert!(!matches.is_present("time"));
+        assert_eq!(matches.positional(0), Some("--time"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse(&["--tim"]),
+            Err(ArgsError::UnknownFlag("--tim".to_string()))
+        );
+        assert_eq!(
+            parse(&["-vx"]),
+            Err(ArgsError::UnknownFlag("-x".to_string()))
+        );
+        assert_eq!(parse(&["1", "-s"]), Err(ArgsError::MissingValue("sample")));
+        assert_eq!(
+            parse(&["--time=1"]),
+            Err(ArgsError::UnexpectedValue("time"))
+        );
+        assert_eq!(
+            parse(&["1", "input", "extra"]),
+            Err(ArgsError::UnexpectedArgument("extra".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_help_stops_parsing() {
+        let matches = parse(&["-h", "--unknown"]).unwrap();
+        assert!(matches.is_present("help"));
+
+        let help = spec().help();
+        assert!(help.contains("Usage: aoc [OPTIONS] [PART] [INPUT]"));
+        assert!(help.contains("\n  -s, --sample <N>  Sample\n"));
+        assert!(help.contains("\n      --time        Time\n"));
+    }
+}
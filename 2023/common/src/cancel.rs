@@ -0,0 +1,127 @@
+use crate::errors::AocError;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/**
+Cooperative cancellation for long-running solvers
+
+When `cli::run` gives up on a solver because of `--timeout`, it cancels the
+[`Token`] of that run. A solver cannot be stopped from the outside, but one
+with a long loop can poll its token and return early, so it stops cleanly
+instead of running on in the background:
+
+```
+use common::cancel;
+use common::errors::AocError;
+
+fn count_steps(limit: u64) -> Result<u64, AocError> {
+    let mut steps = 0;
+    while steps < limit {
+        cancel::check()?;
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+assert_eq!(count_steps(1_000).unwrap(), 1_000);
+
+cancel::cancel();
+assert!(matches!(count_steps(1_000), Err(AocError::Cancelled)));
+cancel::reset();
+```
+
+Each thread polls its own token, which the runner installs on the solver
+thread of every run. A solver abandoned after a timeout therefore stays
+cancelled while the next run, with a fresh token, goes on. Polling is a
+thread-local lookup and an atomic load, cheap enough for the innermost loop;
+threads spawned by a solver have tokens of their own.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    /// A token that has not been cancelled
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks the solver polling this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether this token has been cancelled
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    /// The token polled by [`check`] on this thread
+    static CURRENT: RefCell<Token> = RefCell::new(Token::new());
+}
+
+/// Makes `token` the one this thread polls, as the runner does on each solver thread
+pub fn install(token: Token) {
+    CURRENT.set(token);
+}
+
+/// Asks the solver running on this thread to stop
+pub fn cancel() {
+    CURRENT.with_borrow(Token::cancel);
+}
+
+/// Clears a previous cancellation on this thread, by installing a fresh token
+pub fn reset() {
+    install(Token::new());
+}
+
+/// Whether the solver running on this thread has been asked to stop
+#[inline]
+#[must_use]
+pub fn is_cancelled() -> bool {
+    CURRENT.with_borrow(Token::is_cancelled)
+}
+
+/**
+Returns early from a solver that has been asked to stop
+
+# Errors
+Returns `AocError::Cancelled` once this thread's token has been cancelled
+*/
+#[inline]
+pub fn check() -> Result<(), AocError> {
+    if is_cancelled() {
+        Err(AocError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_tokens_are_per_run() {
+        let abandoned = Token::new();
+        let solver = abandoned.clone();
+        let handle = thread::spawn(move || {
+            install(solver);
+            while check().is_ok() {
+                thread::yield_now();
+            }
+        });
+
+        abandoned.cancel();
+        handle.join().unwrap();
+        // A fresh run on another thread is not affected
+        let next = Token::new();
+        assert!(!next.is_cancelled());
+        assert!(!is_cancelled());
+    }
+}
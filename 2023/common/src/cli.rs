@@ -1,77 +1,1630 @@
+use crate::answer::Answer;
+use crate::answers::{Answers, Verdict};
+use crate::args::{ArgsError, Flag, Matches, Spec};
+use crate::cancel;
 use crate::color::Color;
+use crate::errors::{error_chain, AocError, ChainLink};
+use crate::exit::ExitStatus;
+use crate::history::{self, Entry, History, HISTORY_FILE};
+use crate::input::InputSource;
+use crate::json::Json;
+use crate::locate::{self, sample_files, SAMPLE_PREFIX};
+use crate::log::{self, Level};
+use crate::memory::{self, format_bytes, AllocStats};
+use crate::panics;
+use crate::solution::Solution;
+use crate::timing::{self, format_duration, Stats};
+use crate::watch::Watcher;
 use std::env;
 use std::error::Error;
-use std::fmt::Display;
-use std::process;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// File that `--save-bench` writes the benchmark table to
+const BENCHMARK_FILE: &str = "benchmark.md";
+
+/// Input file used when none is given and nothing is piped to stdin
+const DEFAULT_INPUT: &str = "input";
+
+/// Input file argument that reads the puzzle input from stdin
+const STDIN_ARG: &str = "-";
+
+/// Stack size of the solver thread, roomier than the main thread's for recursive solutions
+const SOLVER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// How long a timed-out solver gets to notice the cancellation before the run ends
+const CANCEL_GRACE: Duration = Duration::from_millis(100);
+
+/// Which part(s) of the puzzle to solve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    /// Parses the part selector given on the command line
+    #[must_use]
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            "both" | "all" => Some(Part::Both),
+            _ => None,
+        }
+    }
+
+    /// Whether part `part` (1 or 2) is selected
+    #[must_use]
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Part::Both, 1 | 2) | (Part::One, 1) | (Part::Two, 2)
+        )
+    }
+
+    /// The selector as written on the command line
+    #[must_use]
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            Part::One => "1",
+            Part::Two => "2",
+            Part::Both => "both",
+        }
+    }
+}
+
+/// How the runner prints its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Colored, human-readable output
+    Text,
+    /// A single JSON object on stdout
+    Json,
+}
+
+impl Format {
+    /// Parses the value of the `--format` flag
+    #[must_use]
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    /// The value of the `--format` flag that selects this format
+    #[must_use]
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// The year and day a solver belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Puzzle {
+    /**
+    Reads the year and day from a day package (or binary) name such as `aoc-2023-8`
+
+    # Examples
+
+    ```
+    use common::cli::Puzzle;
+
+    let puzzle = Puzzle::from_package_name("aoc-2023-8").unwrap();
+    assert_eq!((puzzle.year, puzzle.day), (2023, 8));
+    ```
+    */
+    #[must_use]
+    pub fn from_package_name(name: &str) -> Option<Self> {
+        let mut parts = name.strip_prefix("aoc-")?.split('-');
+        let year = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { year, day })
+    }
+
+    /// Identifies the running day binary from its executable name
+    fn from_current_exe(arg0: &str) -> Option<Self> {
+        Self::from_package_name(Path::new(arg0).file_stem()?.to_str()?)
+    }
+}
+
+/// The flags and arguments of a day binary, from which its help is generated
+fn spec(name: &str) -> Spec {
+    Spec::new(name, "Solves an Advent of Code puzzle")
+        .positional("PART", "1, 2 or both (alias: all); same as --part")
+        .positional("INPUT", "Input file; same as --input")
+        .flag(Flag::with_value("part", "PART", "Part to solve: 1, 2 or both").short('p'))
+        .flag(
+            Flag::with_value(
+                "input",
+                "FILE",
+                "Input file [default: 'input', or stdin when piped; '-' reads stdin]",
+            )
+            .short('i'),
+        )
+        .flag(Flag::with_value("sample", "N", "Use the 'sample_<N>' file as input").short('s'))
+        .flag(Flag::switch(
+            "all-samples",
+            "Solve every 'sample*' file in turn",
+        ))
+        .flag(Flag::switch(
+            "time",
+            "Report read, parse and solve durations",
+        ))
+        .flag(
+            Flag::switch(
+                "watch",
+                "Rebuild and re-run whenever the sources or the input change",
+            )
+            .short('w'),
+        )
+        .flag(Flag::with_value(
+            "bench",
+            "RUNS",
+            "Time parse and solve over many in-process runs",
+        ))
+        .flag(Flag::switch(
+            "save-bench",
+            "Also write the benchmark to 'benchmark.md'",
+        ))
+        .flag(Flag::switch(
+            "no-history",
+            "Don't record the answers in the day's 'history' file",
+        ))
+        .flag(Flag::with_value(
+            "format",
+            "FORMAT",
+            "'text' (default) or 'json' for machine-readable output",
+        ))
+        .flag(Flag::with_value(
+            "timeout",
+            "DURATION",
+            "Give up on a phase after e.g. 30s or 500ms",
+        ))
+        .flag(
+            Flag::switch(
+                "verbose",
+                "Show more log output (repeat for debug and trace)",
+            )
+            .short('v'),
+        )
+        .flag(Flag::switch("quiet", "Only show errors in the log output").short('q'))
+}
+
+/// Options collected from the command line
+#[derive(Clone)]
+struct Options {
+    part: Part,
+    filename: String,
+    /// Where `filename` was found, once looked up in the input directories
+    path: PathBuf,
+    /// Solve every sample file in turn, rather than `filename`
+    all_samples: bool,
+    time: bool,
+    /// Rebuild and re-run on every change instead of running once
+    watch: bool,
+    bench: Option<usize>,
+    save_bench: bool,
+    /// Compare the answers with, and record them in, the day's history file
+    record_history: bool,
+    format: Format,
+    /// Number of `-v` minus number of `-q`
+    verbosity: i8,
+    timeout: Option<Duration>,
+}
+
 /**
-Runs the specified solver with the given command-line arguments
+Whether the input is piped to stdin: a pipe, or a redirected file with content
+
+A terminal, `/dev/null` and other devices are not, so runs from cron, CI or an
+IDE, which often leave stdin as one of those, read the `input` file instead.
+*/
+#[cfg(unix)]
+fn stdin_is_piped() -> bool {
+    use std::os::fd::AsFd;
+    use std::os::unix::fs::FileTypeExt;
+
+    let Ok(fd) = io::stdin().as_fd().try_clone_to_owned() else {
+        return false;
+    };
+    fs::File::from(fd).metadata().is_ok_and(|metadata| {
+        metadata.file_type().is_fifo() || (metadata.is_file() && metadata.len() > 0)
+    })
+}
+
+/// Whether the input is piped to stdin: anything but a terminal
+#[cfg(not(unix))]
+fn stdin_is_piped() -> bool {
+    use std::io::IsTerminal;
+
+    !io::stdin().is_terminal()
+}
+
+impl Options {
+    /// Reads the options from parsed arguments, checking their values and combinations
+    fn from_matches(matches: &Matches) -> Result<Self, ArgsError> {
+        let invalid = |name: &str, value: &str, expected: &str| ArgsError::InvalidValue {
+            name: name.to_string(),
+            value: value.to_string(),
+            expected: expected.to_string(),
+        };
+
+        let part = match (matches.positional(0), matches.value("part")) {
+            (Some(_), Some(_)) => {
+                return Err(ArgsError::Conflict("[PART]".into(), "--part".into()));
+            }
+            (Some(part), None) | (None, Some(part)) => {
+                Part::from_arg(part).ok_or_else(|| invalid("part", part, "1, 2 or both"))?
+            }
+            // Each sample then runs the parts it has known answers for
+            (None, None) if matches.is_present("all-samples") => Part::Both,
+            (None, None) => return Err(ArgsError::Invalid("Missing part".to_string())),
+        };
+
+        // At most one way of choosing the input
+        let sources: Vec<&str> = [
+            ("[INPUT]", matches.positional(1).is_some()),
+            ("--input", matches.is_present("input")),
+            ("--sample", matches.is_present("sample")),
+            ("--all-samples", matches.is_present("all-samples")),
+        ]
+        .into_iter()
+        .filter_map(|(name, given)| given.then_some(name))
+        .collect();
+        if let [first, second, ..] = sources[..] {
+            return Err(ArgsError::Conflict(first.into(), second.into()));
+        }
+
+        let filename = if let Some(filename) = matches.positional(1).or(matches.value("input")) {
+            filename.to_string()
+        } else if let Some(n) = matches.value("sample") {
+            n.parse::<u32>()
+                .map_err(|_| invalid("sample number", n, "a number such as 1"))?;
+            format!("{SAMPLE_PREFIX}_{n}")
+        } else if stdin_is_piped()
+            && !matches.is_present("all-samples")
+            && !matches.is_present("watch")
+        {
+            STDIN_ARG.to_string()
+        } else {
+            DEFAULT_INPUT.to_string()
+        };
+
+        let bench = match matches.value("bench") {
+            Some(runs) => Some(
+                runs.parse::<usize>()
+                    .ok()
+                    .filter(|&runs| runs > 0)
+                    .ok_or_else(|| invalid("number of runs", runs, "a positive number"))?,
+            ),
+            None => None,
+        };
+        let save_bench = matches.is_present("save-bench");
+        if save_bench && bench.is_none() {
+            return Err(ArgsError::Invalid(
+                "--save-bench requires --bench <RUNS>".to_string(),
+            ));
+        }
+
+        let format = match matches.value("format") {
+            Some(value) => {
+                Format::from_arg(value).ok_or_else(|| invalid("format", value, "text or json"))?
+            }
+            None => Format::Text,
+        };
+
+        let timeout = match matches.value("timeout") {
+            Some(value) => Some(
+                timing::parse_duration(value)
+                    .filter(|limit| !limit.is_zero())
+                    .ok_or_else(|| invalid("timeout", value, "e.g. 30s or 500ms"))?,
+            ),
+            None => None,
+        };
+
+        if matches.is_present("watch") && filename == STDIN_ARG {
+            return Err(ArgsError::Invalid(
+                "--watch cannot read the input from stdin".to_string(),
+            ));
+        }
+
+        let count = |flag| i8::try_from(matches.count(flag)).unwrap_or(i8::MAX);
+        let verbosity = count("verbose").saturating_sub(count("quiet"));
+
+        Ok(Self {
+            part,
+            path: PathBuf::from(&filename),
+            filename,
+            all_samples: matches.is_present("all-samples"),
+            time: matches.is_present("time"),
+            watch: matches.is_present("watch"),
+            bench,
+            save_bench,
+            record_history: !matches.is_present("no-history"),
+            format,
+            verbosity,
+            timeout,
+        })
+    }
+
+    /// Arguments that give these options to another run, such as the ones `--watch` starts
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--part".to_string(), self.part.as_arg().to_string()];
+        if self.all_samples {
+            args.push("--all-samples".to_string());
+        } else {
+            args.extend(["--input".to_string(), self.filename.clone()]);
+        }
+
+        let switches = [
+            ("--time", self.time),
+            ("--watch", self.watch),
+            ("--save-bench", self.save_bench),
+            ("--no-history", !self.record_history),
+        ];
+        for (flag, given) in switches {
+            if given {
+                args.push(flag.to_string());
+            }
+        }
+        if let Some(runs) = self.bench {
+            args.extend(["--bench".to_string(), runs.to_string()]);
+        }
+        if let Some(limit) = self.timeout {
+            args.extend(["--timeout".to_string(), format!("{}s", limit.as_secs_f64())]);
+        }
+        args.extend(["--format".to_string(), self.format.as_arg().to_string()]);
+
+        let verbosity = if self.verbosity < 0 { "-q" } else { "-v" };
+        args.extend((0..self.verbosity.unsigned_abs()).map(|_| verbosity.to_string()));
+        args
+    }
+
+    fn reads_stdin(&self) -> bool {
+        self.filename == STDIN_ARG
+    }
+
+    /// Where the input comes from, as shown in reports
+    fn input_name(&self) -> &str {
+        if self.reads_stdin() {
+            "stdin"
+        } else {
+            &self.filename
+        }
+    }
+
+    /// Where the puzzle input is read from
+    fn source(&self) -> InputSource {
+        if self.reads_stdin() {
+            InputSource::Stdin
+        } else {
+            InputSource::File(self.path.clone())
+        }
+    }
+}
+
+/// Why a phase failed, and the status the process exits with because of it
+struct Failure {
+    chain: Vec<ChainLink>,
+    status: ExitStatus,
+}
+
+impl Failure {
+    fn new(err: &(dyn Error + 'static), status: ExitStatus) -> Self {
+        Self {
+            chain: error_chain(err),
+            status,
+        }
+    }
+}
+
+/// The outcome of solving one part
+struct PartReport {
+    part: u8,
+    answer: Result<Answer, Failure>,
+    verdict: Option<Verdict>,
+    /// The last answer recorded for this input and part, when it was different
+    previous: Option<Entry>,
+    elapsed: Duration,
+}
+
+impl PartReport {
+    /// Whether the part is not implemented yet, rather than failing
+    fn is_unsolved(&self) -> bool {
+        matches!(&self.answer, Err(failure) if failure.status == ExitStatus::Unsolved)
+    }
+}
+
+/// Everything a run produced, rendered as text or JSON at the end
+struct Report {
+    puzzle: Option<Puzzle>,
+    options: Options,
+    answers: Answers,
+    /// Where the answers are recorded, unless `--no-history`
+    history_path: Option<PathBuf>,
+    history: History,
+    /// The hash of the input, once read, to look its history up by
+    input_hash: Option<String>,
+    timings: Vec<(&'static str, Duration)>,
+    /// What each phase allocated, when allocations are counted
+    memory: Vec<(&'static str, AllocStats)>,
+    parts: Vec<PartReport>,
+    error: Option<Failure>,
+    bench: Vec<(&'static str, Stats)>,
+}
+
+impl Report {
+    fn new(puzzle: Option<Puzzle>, options: Options, history_path: Option<PathBuf>) -> Self {
+        // Piped input has no file name to look its answers up by
+        let answers = if options.reads_stdin() {
+            Answers::default()
+        } else {
+            Answers::load_for(&options.path).unwrap_or_else(|e| {
+                crate::warn!(
+                    "ignoring the known answers of {}: {e}",
+                    options.path.display()
+                );
+                Answers::default()
+            })
+        };
+
+        let history = history_path.as_ref().map_or_else(History::default, |path| {
+            History::load(path).unwrap_or_else(|e| {
+                crate::warn!("ignoring the history in {}: {e}", path.display());
+                History::default()
+            })
+        });
+
+        Self {
+            puzzle,
+            options,
+            answers,
+            history_path,
+            history,
+            input_hash: None,
+            timings: Vec::new(),
+            memory: Vec::new(),
+            parts: Vec::new(),
+            error: None,
+            bench: Vec::new(),
+        }
+    }
+
+    /// Whether every phase succeeded and no answer contradicts the answers file
+    fn succeeded(&self) -> bool {
+        self.status() == ExitStatus::Success
+    }
+
+    /**
+    The status of the first failure, in the order the phases ran
+
+    An unsolved part is only reported when nothing else failed.
+    */
+    fn status(&self) -> ExitStatus {
+        let failures =
+            self.error
+                .iter()
+                .map(|failure| failure.status)
+                .chain(
+                    self.parts
+                        .iter()
+                        .filter_map(|p| match (&p.answer, &p.verdict) {
+                            (Err(failure), _) => Some(failure.status),
+                            (Ok(_), Some(Verdict::Mismatch { .. })) => {
+                                Some(ExitStatus::WrongAnswer)
+                            }
+                            (Ok(_), _) => None,
+                        }),
+                );
+
+        let mut status = ExitStatus::Success;
+        for failure in failures {
+            if failure != ExitStatus::Unsolved {
+                return failure;
+            }
+            status = ExitStatus::Unsolved;
+        }
+        status
+    }
+
+    /// Records a failed read or parse phase
+    fn fail(&mut self, failure: Failure) {
+        if self.options.format == Format::Text {
+            print_chain(&failure.chain);
+        }
+        self.error = Some(failure);
+    }
+
+    /// Records a phase that did not finish within `limit`
+    fn time_out(&mut self, phase: Phase, limit: Duration, elapsed: Duration) {
+        let failure = Failure::new(&AocError::TimedOut(limit), ExitStatus::TimedOut);
+        match phase {
+            Phase::Part(part) => self.add_part(part, Err(failure), elapsed),
+            Phase::Read | Phase::Parse => {
+                self.timings.push((phase.label(), elapsed));
+                self.fail(failure);
+            }
+        }
+    }
+
+    /// Records the result of solving a part, printing it right away in text mode
+    fn add_part(&mut self, part: u8, answer: Result<Answer, Failure>, elapsed: Duration) {
+        let verdict = answer.as_ref().ok().map(|answer| {
+            let section = Answers::section_for(&self.options.path);
+            self.answers.check(&section, part, answer)
+        });
+        let previous = match (&answer, &self.input_hash) {
+            (Ok(answer), Some(hash)) => self
+                .history
+                .last(hash, part)
+                .filter(|entry| entry.answer != answer.to_string())
+                .cloned(),
+            _ => None,
+        };
+
+        if self.options.format == Format::Text {
+            // Samples don't all solve the same parts, so they always say which
+            let label =
+                (self.options.part == Part::Both || self.options.all_samples).then_some(part);
+            match (&answer, &verdict) {
+                (Ok(answer), Some(verdict)) => {
+                    // A grid that isn't made of letters starts on its own line
+                    let separator = if answer.is_multiline() { "\n" } else { "" };
+                    let answer = format!("{separator}{}", Color::Blue.wrap(&answer.to_string()));
+                    let verdict = match verdict {
+                        Verdict::Match => Color::Green.wrap("✓"),
+                        Verdict::Mismatch { expected } => {
+                            Color::Red.wrap(&format!("✗ expected {expected}"))
+                        }
+                        Verdict::Unknown => Color::Dim.wrap("(unknown)"),
+                    };
+                    match label {
+                        Some(n) => println!("Part {n}: {answer} {verdict}"),
+                        None => println!("Solution: {answer} {verdict}"),
+                    }
+                }
+                (Ok(_), None) => unreachable!("every answer gets a verdict"),
+                (Err(failure), _) if failure.status == ExitStatus::Unsolved => {
+                    let unsolved = Color::Yellow.wrap("unsolved");
+                    match label {
+                        Some(n) => println!("Part {n}: {unsolved}"),
+                        None => println!("Solution: {unsolved}"),
+                    }
+                }
+                (Err(failure), _) => {
+                    if let Some(n) = label {
+                        eprintln!("Part {n} failed");
+                    }
+                    print_chain(&failure.chain);
+                }
+            }
+        }
+
+        // On stderr even with `--format json`: this is easy to miss otherwise
+        if let Some(entry) = &previous {
+            let revision = entry.revision.as_deref().unwrap_or("unknown revision");
+            let warning = format!(
+                "Warning: the answer to part {part} changed, it was {} on {} ({revision})",
+                entry.answer, entry.timestamp
+            );
+            eprintln!("{}", Color::Yellow.wrap(&warning));
+        }
+
+        self.timings.push((part_label(part), elapsed));
+        self.parts.push(PartReport {
+            part,
+            answer,
+            verdict,
+            previous,
+            elapsed,
+        });
+    }
+
+    /// Appends the answers of a successful run to the history file
+    fn record_history(&self) {
+        let (Some(path), Some(input_hash)) = (&self.history_path, &self.input_hash) else {
+            return;
+        };
+        let answers: Vec<(u8, &Answer)> = self
+            .parts
+            .iter()
+            .filter_map(|p| Some((p.part, p.answer.as_ref().ok()?)))
+            .collect();
+        // Nothing to record, so no need to ask git for the revision
+        if answers.is_empty() {
+            return;
+        }
+
+        let timestamp = history::timestamp(SystemTime::now());
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+        let revision = history::git_revision(dir.unwrap_or_else(|| Path::new(".")));
+
+        let entries: Vec<Entry> = answers
+            .into_iter()
+            .map(|(part, answer)| Entry {
+                timestamp: timestamp.clone(),
+                input_hash: input_hash.clone(),
+                input: self.options.input_name().to_string(),
+                part,
+                revision: revision.clone(),
+                answer: answer.to_string(),
+            })
+            .collect();
+        if let Err(e) = History::append(path, &entries) {
+            crate::warn!("could not record the answers in {}: {e}", path.display());
+        }
+    }
+
+    /// The lines this run adds to the `--all-samples` summary
+    fn sample_rows(&self) -> Vec<SampleRow> {
+        let input = self.options.input_name().to_string();
+        if self.parts.is_empty() {
+            // Reading or parsing failed before any part was solved
+            return vec![SampleRow {
+                input,
+                part: None,
+                answer: None,
+                verdict: None,
+                unsolved: self.status() == ExitStatus::Unsolved,
+            }];
+        }
+
+        self.parts
+            .iter()
+            .map(|p| SampleRow {
+                input: input.clone(),
+                part: Some(p.part),
+                answer: p.answer.as_ref().ok().map(ToString::to_string),
+                verdict: p.verdict.clone(),
+                unsolved: p.is_unsolved(),
+            })
+            .collect()
+    }
+
+    /// Prints whatever has not been printed yet
+    fn finish(&self) {
+        match self.options.format {
+            Format::Text => {
+                if self.options.time {
+                    print_timings(&self.timings, &self.memory);
+                }
+                if !self.bench.is_empty() {
+                    print_bench(&self.bench);
+                }
+            }
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        let nanos = |d: Duration| Json::from(d.as_nanos());
+        let chain = |chain: &[ChainLink]| {
+            Json::array(chain.iter().map(|link| {
+                Json::object([
+                    ("variant", Json::from(link.variant.as_str())),
+                    ("message", Json::from(link.message.as_str())),
+                ])
+            }))
+        };
+
+        let parts = self.parts.iter().map(|p| {
+            let (answer, error) = match &p.answer {
+                Ok(answer) => (Json::from(answer.to_string()), Json::Null),
+                Err(failure) => (Json::Null, chain(&failure.chain)),
+            };
+            let expected = match &p.verdict {
+                Some(Verdict::Mismatch { expected }) => Json::from(expected.as_str()),
+                Some(Verdict::Match) => answer.clone(),
+                _ => Json::Null,
+            };
+            Json::object([
+                ("part", Json::from(p.part)),
+                ("answer", answer),
+                ("expected", expected),
+                (
+                    "verdict",
+                    Json::from(p.verdict.as_ref().map(Verdict::as_str)),
+                ),
+                (
+                    "previous_answer",
+                    Json::from(p.previous.as_ref().map(|entry| entry.answer.as_str())),
+                ),
+                ("unsolved", Json::from(p.is_unsolved())),
+                ("time_ns", nanos(p.elapsed)),
+                ("error", error),
+            ])
+        });
+
+        let timings = self
+            .timings
+            .iter()
+            .map(|(phase, elapsed)| (phase.replace(' ', "_"), nanos(*elapsed)));
+
+        let memory = self.memory.iter().map(|(phase, stats)| {
+            let stats = Json::object([
+                ("allocations", Json::from(stats.allocations)),
+                ("bytes", Json::from(stats.bytes)),
+                ("peak_bytes", Json::from(stats.peak_bytes)),
+            ]);
+            (phase.replace(' ', "_"), stats)
+        });
+
+        let bench = self.bench.iter().map(|(phase, stats)| {
+            Json::object([
+                ("phase", Json::from(*phase)),
+                ("runs", Json::from(stats.runs)),
+                ("min_ns", nanos(stats.min)),
+                ("median_ns", nanos(stats.median)),
+                ("mean_ns", nanos(stats.mean)),
+                ("p95_ns", nanos(stats.p95)),
+                ("std_dev_ns", nanos(stats.std_dev)),
+            ])
+        });
+
+        Json::object([
+            ("year", Json::from(self.puzzle.map(|p| p.year))),
+            ("day", Json::from(self.puzzle.map(|p| p.day))),
+            ("part", Json::from(self.options.part.as_arg())),
+            ("input", Json::from(self.options.input_name())),
+            ("success", Json::from(self.succeeded())),
+            ("exit_code", Json::from(self.status().code())),
+            ("results", Json::array(parts)),
+            ("timings_ns", Json::object(timings)),
+            ("memory", Json::object(memory)),
+            ("peak_rss_bytes", Json::from(memory::peak_rss())),
+            ("bench", Json::array(bench)),
+            (
+                "error",
+                self.error
+                    .as_ref()
+                    .map_or(Json::Null, |failure| chain(&failure.chain)),
+            ),
+        ])
+    }
+}
+
+/**
+Runs the solution `S` with the given command-line arguments
+
+The part is given as `<PART>` or `--part`, and the input as `[INPUT]`,
+`--input <FILE>` or `--sample <N>` (for `sample_<N>`). `--all-samples` solves
+every `sample*` file of the input directory in turn. `--help` lists every
+flag, and mistakes are reported with the usage. The flags are declared in one
+place, as an [`args::Spec`](crate::args::Spec), from which the help is generated.
+
+The input is read once and handed to [`Solution::parse`], and the parsed value
+is passed to the selected part solver(s). With the `both` (or `all`) selector the
+same parsed value feeds part 1 and then part 2. Passing `--time` prints how long
+reading, parsing and each part took.
+
+The input comes from the given file, or from stdin when the file is `-`.
+When no file is given, stdin is used if it is piped and the `input` file
+otherwise, so `generate | cargo run -- 1` works without extra arguments.
+
+A relative input file is looked for in the day's crate directory, then in the
+directory named by `AOC_INPUT_DIR`, then in the current directory (see
+[`locate`]), so a day runs the same from its folder and from the workspace
+root. The crate directory is only known when the binary starts through
+[`run!`](crate::run); a missing file is reported with every path tried and
+the sample files that do exist.
+
+`--bench <runs>` additionally benchmarks parsing and the selected part(s)
+in-process: after a few warmup runs, each phase is timed `runs` times and the
+min, median, mean, p95 and standard deviation are reported. `--save-bench`
+also writes the results to `benchmark.md`.
+
+Each answer is checked against the `answers` file next to the input file (see
+[`Answers`]) and reported as a match, a mismatch showing the expected answer,
+or unknown. A mismatch makes the process exit with a non-zero status.
+
+Every successful run appends its answers to the `history` file next to the
+input (see [`History`]), with the input's hash, the git revision and the time.
+An answer that differs from the last one recorded for the same input and part
+is reported with a warning, even when it matches the answers file, so a change
+to shared code that breaks a solved day doesn't go unnoticed. `--no-history`
+neither checks nor records anything.
+
+`--timeout <duration>` (such as `30s` or `500ms`) limits how long reading,
+parsing and each part may take. The solver runs on its own thread, and a phase
+that runs out of time is reported as timed out, with the time spent, instead of
+hanging. Solvers with long loops can poll [`cancel::check`] to stop cleanly.
+Benchmarks are not subject to the limit.
+
+A solver that panics while parsing or solving fails like one returning an
+error: the panic message and location are reported as an [`AocError::Panic`]
+and the process exits with a non-zero status rather than aborting, so the
+other part still runs and a script running several days keeps going.
+
+`--watch` keeps the runner going: whenever the day's sources, `common`'s or
+the input and its known answers change, the day is rebuilt and run again on a
+cleared screen, with its timings. Changes are found by polling, so it works the
+same everywhere.
 
-# Arguments
-* `part_1` - A function that solves part 1
-* `part_2` - A function that solves part 2
+The exit status tells failures apart: bad arguments, a missing, empty or
+invalid input, a failing part, a wrong answer, a timeout and a panic each have
+their own code (see [`ExitStatus`]). A part returning
+[`AocError::NotImplemented`], as generated days do, is shown as unsolved rather
+than failed, and only decides the exit status when nothing else failed.
+
+Solvers log through the [`log`](crate::log) macros. Only warnings and errors
+are shown by default; each `-v` shows one more level (info, debug, trace) and
+`-q` hides warnings. Log messages go to stderr.
+
+When `common` is built with the `alloc-stats` feature, `--time` also shows
+how many allocations each phase made, how many bytes they requested and the
+peak of live bytes (see [`memory`]). The peak RSS of the process is shown
+either way, where the platform reports it.
+
+`--format json` replaces the colored output with a single JSON object holding
+the year, day, part, input file, exit code, answers, timings and, on failure,
+the error chain with the variant and message of each level.
 
 # Example
 
-```
-use common::cli;
+```no_run
+use common::errors::AocError;
+use common::input::Input;
+use common::solution::Solution;
+
+struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        Ok(input.as_lines()?)
+    }
+
+    fn part_1(lines: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        Ok(lines.len())
+    }
 
-fn part1_solver(filename: &str) -> Result<u32, AocError> {
-    // solution implementation
-    Ok(42)
+    fn part_2(lines: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        Ok(lines.concat())
+    }
+}
+
+fn main() {
+    common::run!(Day1);
+}
+```
+*/
+pub fn run<S: Solution + 'static>() {
+    run_with::<S>(None);
 }
 
-fn part2_solver(filename: &str) -> Result<u32, AocError> {
-    // solution implementation
-    Ok(24)
+/// Runs the solution `S`, looking for its input files in `crate_dir` first
+pub fn run_in<S: Solution + 'static>(crate_dir: &str) {
+    run_with::<S>(Some(Path::new(crate_dir)));
 }
 
+/**
+Runs the solution `S` of the day crate it is called from
+
+Expands to [`cli::run_in`](crate::cli::run_in) with the crate's manifest
+directory, so the day's input files are found wherever it is run from.
+
+```no_run
+# use common::{errors::AocError, input::Input, solution::Solution};
+# struct Day1;
+# impl Solution for Day1 {
+#     type Parsed = ();
+#     type Answer1 = u32;
+#     type Answer2 = u32;
+#     fn parse(_: &Input) -> Result<(), AocError> { Ok(()) }
+#     fn part_1(_: &()) -> Result<u32, AocError> { Ok(1) }
+#     fn part_2(_: &()) -> Result<u32, AocError> { Ok(2) }
+# }
 fn main() {
-    cli::run_funcs(part1_solver, part2_solver);
+    common::run!(Day1);
 }
 ```
 */
-pub fn run<F1, F2, T, E>(part_1: F1, part_2: F2)
-where
-    F1: Fn(&str) -> Result<T, E>,
-    F2: Fn(&str) -> Result<T, E>,
-    T: Display,
-    E: Error + Display,
-{
+#[macro_export]
+macro_rules! run {
+    ($solution:ty) => {
+        $crate::cli::run_in::<$solution>(env!("CARGO_MANIFEST_DIR"))
+    };
+}
+
+fn run_with<S: Solution + 'static>(crate_dir: Option<&Path>) {
     let args: Vec<String> = env::args().collect();
+    let name = Path::new(&args[0]).file_name().map_or_else(
+        || args[0].clone(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let spec = spec(&name);
+
+    let matches = spec
+        .parse(&args[1..])
+        .unwrap_or_else(|e| usage_error(&spec, &e));
+    if matches.is_present("help") {
+        print!("{}", spec.help());
+        return;
+    }
+    if matches.is_present("version") {
+        println!("{name} (common {})", env!("CARGO_PKG_VERSION"));
+        return;
+    }
+    let options = Options::from_matches(&matches).unwrap_or_else(|e| usage_error(&spec, &e));
+
+    log::set_level(Level::from_verbosity(options.verbosity));
 
-    if args.len() < 2 {
-        eprintln!("Usage: {} <part> [input_file]", args[0]);
-        eprintln!("  part: 1 or 2");
-        eprintln!("  input_file: optional, defaults to 'input'");
-        process::exit(1);
+    let dirs = locate::search_dirs(crate_dir);
+    if options.watch {
+        watch(&args, &options, crate_dir, &dirs);
     }
 
-    let part = &args[1];
-    let filename = args.get(2).map_or("input", |s| s);
+    let format = options.format;
+    let runs = if options.all_samples {
+        let Some((dir, samples)) = find_samples(&dirs) else {
+            let dirs: Vec<String> = dirs.iter().map(|dir| dir.display().to_string()).collect();
+            eprintln!("No '{SAMPLE_PREFIX}*' file found in {}", dirs.join(", "));
+            ExitStatus::InputMissing.exit();
+        };
+        samples
+            .into_iter()
+            .map(|filename| {
+                let path = dir.join(&filename);
+                Options {
+                    part: sample_part(options.part, &path),
+                    filename,
+                    path,
+                    ..options.clone()
+                }
+            })
+            .collect()
+    } else {
+        vec![options]
+    };
+
+    let mut status = ExitStatus::Success;
+    let mut summary = Vec::new();
+    for (i, mut options) in runs.into_iter().enumerate() {
+        if options.all_samples && options.format == Format::Text {
+            let separator = if i == 0 { "" } else { "\n" };
+            println!("{separator}{}", Color::Bold.wrap(&options.filename));
+        }
+
+        let missing = if options.reads_stdin() {
+            None
+        } else {
+            match locate::locate(&options.filename, &dirs) {
+                Ok(path) => {
+                    options.path = path;
+                    None
+                }
+                Err(e) => Some(e),
+            }
+        };
+
+        // Piped input is recorded in the day's own history file
+        let history_path = options.record_history.then(|| {
+            if options.reads_stdin() {
+                dirs[0].join(HISTORY_FILE)
+            } else {
+                History::path_for(&options.path)
+            }
+        });
+        let mut report = Report::new(Puzzle::from_current_exe(&args[0]), options, history_path);
+        match missing {
+            Some(e) => report.fail(Failure::new(&e, ExitStatus::for_input_error(&e))),
+            None => solve::<S>(&mut report),
+        }
+        report.finish();
 
-    let result = match part.as_str() {
-        "1" => part_1(filename),
-        "2" => part_2(filename),
-        _ => {
-            eprintln!("Invalid part number. Use 1 or 2");
-            process::exit(1);
+        // Like within a run, an unsolved part doesn't hide a later failure
+        let run_status = report.status();
+        if matches!(status, ExitStatus::Success | ExitStatus::Unsolved)
+            && run_status != ExitStatus::Success
+        {
+            status = run_status;
         }
+        // The parts that are solved still get recorded
+        if matches!(run_status, ExitStatus::Success | ExitStatus::Unsolved) {
+            report.record_history();
+        }
+        if report.succeeded() && report.options.save_bench {
+            save_bench(&report.bench, report.options.input_name(), &dirs[0]);
+        }
+        if report.options.all_samples {
+            summary.extend(report.sample_rows());
+        }
+    }
+
+    if !summary.is_empty() && format == Format::Text {
+        print_sample_summary(&summary);
+    }
+
+    if status != ExitStatus::Success {
+        status.exit();
+    }
+}
+
+/**
+Rebuilds and re-runs this day whenever its sources, `common` or its input change
+
+This binary supervises copies of itself: each change triggers `cargo build` of
+the day, then a run of the rebuilt binary with the same arguments, without
+`--watch` and with `--time`, after clearing the screen. Build errors are shown
+as cargo prints them, and the next change tries again. Never returns; stop it
+with Ctrl-C.
+*/
+fn watch(args: &[String], options: &Options, crate_dir: Option<&Path>, dirs: &[PathBuf]) -> ! {
+    // Resolved before the first rebuild replaces the executable
+    let exe = env::current_exe().unwrap_or_else(|_| PathBuf::from(&args[0]));
+    let package = Path::new(&args[0])
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned());
+    // `CARGO_MANIFEST_DIR` is set by `cargo run`; otherwise the day is assumed
+    // to be run from its folder
+    let day_dir = crate_dir.map_or_else(
+        || env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from("."), PathBuf::from),
+        Path::to_path_buf,
+    );
+    let common_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    // An input that doesn't exist yet is watched where the first directory would have it
+    let inputs: Vec<PathBuf> = if options.all_samples {
+        find_samples(dirs)
+            .map(|(dir, samples)| samples.iter().map(|name| dir.join(name)).collect())
+            .unwrap_or_default()
+    } else {
+        vec![locate::locate(&options.filename, dirs)
+            .unwrap_or_else(|_| dirs[0].join(&options.filename))]
     };
+    let mut watched = vec![day_dir.join("src"), common_dir.join("src")];
+    for input in &inputs {
+        watched.push(input.clone());
+        watched.push(Answers::path_for(input));
+        watched.push(Answers::sidecar_for(input));
+    }
 
-    match result {
-        Ok(answer) => println!("Solution: {}", Color::Blue.wrap(&answer.to_string())),
-        Err(e) => {
-            eprintln!("Error: {e}");
+    // Built from the parsed options, so `-w` grouped with other short flags is dropped too
+    let child_args = Options {
+        watch: false,
+        time: options.time || options.format == Format::Text,
+        ..options.clone()
+    }
+    .to_args();
+
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut watcher = Watcher::new(&watched);
+    let mut changed: Vec<PathBuf> = Vec::new();
+
+    loop {
+        // Clear the screen and move the cursor to the top left corner
+        print!("\x1b[2J\x1b[H");
+        for path in &changed {
+            println!(
+                "{}",
+                Color::Dim.wrap(&format!("changed: {}", path.display()))
+            );
+        }
+        let _ = io::stdout().flush();
+
+        let mut build = Command::new(&cargo);
+        build.args(["build", "--quiet"]).current_dir(&day_dir);
+        if let Some(package) = &package {
+            build.args(["--package", package]);
+        }
+        if !cfg!(debug_assertions) {
+            build.arg("--release");
+        }
+
+        match build.status() {
+            Ok(status) if status.success() => match Command::new(&exe).args(&child_args).status() {
+                Ok(status) => {
+                    let result = if status.success() {
+                        Color::Green.wrap("ok")
+                    } else if status.code() == Some(ExitStatus::Unsolved.code().into()) {
+                        Color::Yellow.wrap("unsolved")
+                    } else {
+                        let code = status
+                            .code()
+                            .map_or_else(|| "signal".to_string(), |c| c.to_string());
+                        Color::Red.wrap(&format!("failed ({code})"))
+                    };
+                    println!("\n{result}");
+                }
+                Err(e) => eprintln!("Could not run {}: {e}", exe.display()),
+            },
+            Ok(_) => println!("\n{}", Color::Red.wrap("build failed")),
+            Err(e) => eprintln!("Could not run cargo: {e}"),
+        }
 
-            let mut source = e.source();
-            let mut level = 1;
-            while let Some(err) = source {
-                eprintln!("Caused by ({level}): {err}");
-                source = err.source();
-                level += 1;
+        println!(
+            "{}",
+            Color::Dim.wrap("Watching for changes, Ctrl-C to stop...")
+        );
+        changed = watcher.wait();
+    }
+}
+
+/// Reports a command-line mistake, with the usage, and exits
+fn usage_error(spec: &Spec, error: &ArgsError) -> ! {
+    eprintln!("{}: {error}", Color::Red.wrap("error"));
+    eprintln!();
+    eprintln!("{}", spec.usage());
+    eprintln!();
+    eprintln!("For more information, try '--help'.");
+    ExitStatus::Usage.exit();
+}
+
+/// The sample files of the first of `dirs` that has any, with that directory
+fn find_samples(dirs: &[PathBuf]) -> Option<(&Path, Vec<String>)> {
+    dirs.iter().find_map(|dir| {
+        let samples = sample_files(dir).ok()?;
+        (!samples.is_empty()).then_some((dir.as_path(), samples))
+    })
+}
+
+/**
+The part(s) to solve on a sample: those of `selected` that have a known answer
+
+Samples are often written for one part only, so solving the other part would
+fail or give a meaningless answer. A sample with no known answer for the
+selected part(s) is solved anyway, and its answers reported as unknown.
+*/
+fn sample_part(selected: Part, sample: &Path) -> Part {
+    let answers = Answers::load_for(sample).unwrap_or_default();
+    let known = answers.known_parts(&Answers::section_for(sample));
+    let wanted = |part| known.contains(&part) && selected.includes(part);
+    match (wanted(1), wanted(2)) {
+        (true, true) => Part::Both,
+        (true, false) => Part::One,
+        (false, true) => Part::Two,
+        (false, false) => selected,
+    }
+}
+
+/// One line of the summary printed after `--all-samples`
+struct SampleRow {
+    input: String,
+    part: Option<u8>,
+    answer: Option<String>,
+    verdict: Option<Verdict>,
+    /// The part, or parsing, is not implemented yet
+    unsolved: bool,
+}
+
+/**
+Prints whether each sample and part passed, with the answer and expected answer
+
+An answer passes when it matches the known answer, and fails when it differs
+from it or could not be computed. Parts that are not implemented yet are
+counted apart, as unsolved.
+*/
+fn print_sample_summary(rows: &[SampleRow]) {
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let expected = match &row.verdict {
+                Some(Verdict::Mismatch { expected }) => expected.clone(),
+                Some(Verdict::Match) => row.answer.clone().unwrap_or_default(),
+                _ => "-".to_string(),
+            };
+            [
+                row.input.clone(),
+                row.part
+                    .map_or_else(|| "-".to_string(), |part| part.to_string()),
+                row.answer
+                    .clone()
+                    .unwrap_or_else(|| if row.unsolved { "unsolved" } else { "error" }.to_string()),
+                expected,
+            ]
+        })
+        .collect();
+
+    let headers = ["input", "part", "answer", "expected"];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: [&str; 4]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+
+    println!();
+    println!("{}", Color::Bold.wrap("Samples"));
+    println!("{}", Color::Dim.wrap(&format!("{}  result", line(headers))));
+
+    let (mut passed, mut failed, mut unknown, mut unsolved) = (0, 0, 0, 0);
+    for (row, cells) in rows.iter().zip(&cells) {
+        let result = match &row.verdict {
+            _ if row.unsolved => {
+                unsolved += 1;
+                Color::Yellow.wrap("unsolved")
+            }
+            Some(Verdict::Match) => {
+                passed += 1;
+                Color::Green.wrap("pass")
+            }
+            Some(Verdict::Unknown) => {
+                unknown += 1;
+                Color::Dim.wrap("unknown")
+            }
+            Some(Verdict::Mismatch { .. }) | None => {
+                failed += 1;
+                Color::Red.wrap("fail")
+            }
+        };
+        println!("{}  {result}", line(cells.each_ref().map(String::as_str)));
+    }
+    if unsolved == 0 {
+        println!("{passed} passed, {failed} failed, {unknown} unknown");
+    } else {
+        println!("{passed} passed, {failed} failed, {unknown} unknown, {unsolved} unsolved");
+    }
+}
+
+/// A step of a run, as reported by the solver thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Read,
+    Parse,
+    Part(u8),
+}
+
+impl Phase {
+    fn label(self) -> &'static str {
+        match self {
+            Phase::Read => "read",
+            Phase::Parse => "parse",
+            Phase::Part(part) => part_label(part),
+        }
+    }
+}
+
+/// What the solver thread sends back to the runner, as it goes
+enum Event {
+    /// A phase is starting, so its time limit starts running
+    Started(Phase),
+    /// Reading or parsing the input finished, successfully unless there is an error
+    Prepared(Phase, Option<Failure>, Duration),
+    /// A part finished with an answer or an error
+    Solved(u8, Result<Answer, Failure>, Duration),
+    /// What a phase allocated, sent just before it finishes when allocations are counted
+    Allocated(Phase, AllocStats),
+    /// The benchmark results, once asked for
+    Bench(Vec<(&'static str, Stats)>),
+    /// The hash of the input, once read, when the answers are recorded
+    Hashed(String),
+}
+
+/**
+Runs `solve_phases` on a solver thread and records every phase in `report`
+
+The runner only waits up to `--timeout` for each phase. When a phase runs out
+of time it is reported as timed out, the solver is asked to stop through
+[`cancel`], and the run ends without waiting for it.
+*/
+fn solve<S: Solution + 'static>(report: &mut Report) {
+    // Each run has its own token, so a solver that timed out on a previous
+    // input stays cancelled while this one runs
+    let token = cancel::Token::new();
+    let solver_token = token.clone();
+    let (events_tx, events) = mpsc::channel();
+    let (bench_tx, bench_rx) = mpsc::channel();
+    let options = report.options.clone();
+    let solver = thread::Builder::new()
+        .name("solver".to_string())
+        .stack_size(SOLVER_STACK_SIZE)
+        .spawn(move || {
+            cancel::install(solver_token);
+            // Parsing and solving catch their own panics; this catches the
+            // rest, such as one while benchmarking
+            panics::catch(|| {
+                solve_phases::<S>(&options, &events_tx, &bench_rx);
+                Ok(())
+            })
+        })
+        .expect("failed to spawn the solver thread");
+
+    let n_parts = if report.options.part == Part::Both {
+        2
+    } else {
+        1
+    };
+    let mut current: Option<(Phase, Instant)> = None;
+
+    loop {
+        let event = match (report.options.timeout, current) {
+            (Some(limit), Some((phase, started))) => {
+                match events.recv_timeout(limit.saturating_sub(started.elapsed())) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => {
+                        report.time_out(phase, limit, started.elapsed());
+                        token.cancel();
+                        // Give a cooperative solver the chance to wind down
+                        let _ = events.recv_timeout(CANCEL_GRACE);
+                        return;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            _ => match events.recv() {
+                Ok(event) => event,
+                Err(_) => break,
+            },
+        };
+
+        match event {
+            Event::Started(phase) => current = Some((phase, Instant::now())),
+            Event::Prepared(phase, error, elapsed) => {
+                current = None;
+                report.timings.push((phase.label(), elapsed));
+                if let Some(failure) = error {
+                    report.fail(failure);
+                }
+            }
+            Event::Solved(part, answer, elapsed) => {
+                current = None;
+                report.add_part(part, answer, elapsed);
+                if report.parts.len() == n_parts {
+                    let runs = report.options.bench.filter(|_| report.succeeded());
+                    let _ = bench_tx.send(runs);
+                }
             }
+            Event::Allocated(phase, stats) => report.memory.push((phase.label(), stats)),
+            Event::Bench(rows) => report.bench = rows,
+            Event::Hashed(hash) => report.input_hash = Some(hash),
+        }
+    }
+
+    let result = solver.join().unwrap_or_else(|_| {
+        Err(AocError::Panic {
+            message: "the solver thread panicked".to_string(),
+            location: None,
+        })
+    });
+    if let Err(e) = result {
+        report.fail(Failure::new(&e, ExitStatus::for_solve_error(&e)));
+    }
+}
+
+/**
+Reads, parses and solves the input on the solver thread, reporting each phase
+
+Answers are converted to [`Answer`] here, so that the runner doesn't depend on
+the solver's answer types, and a panic while parsing or solving becomes an
+[`AocError::Panic`]. Once the parts are solved, the runner says whether to
+benchmark them.
+*/
+fn solve_phases<S: Solution>(
+    options: &Options,
+    events: &Sender<Event>,
+    bench: &Receiver<Option<usize>>,
+) {
+    // The runner stops listening once it gives up on a phase
+    let send = |event| {
+        let _ = events.send(event);
+    };
+
+    send(Event::Started(Phase::Read));
+    let (input, elapsed) = measure(Phase::Read, events, || options.source().read());
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            let failure = Failure::new(&e, ExitStatus::for_input_error(&e));
+            return send(Event::Prepared(Phase::Read, Some(failure), elapsed));
+        }
+    };
+    send(Event::Prepared(Phase::Read, None, elapsed));
+    if options.record_history {
+        send(Event::Hashed(history::hash(input.as_string())));
+    }
+
+    send(Event::Started(Phase::Parse));
+    let (parsed, elapsed) = measure(Phase::Parse, events, || panics::catch(|| S::parse(&input)));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let failure = Failure::new(&e, ExitStatus::for_parse_error(&e));
+            return send(Event::Prepared(Phase::Parse, Some(failure), elapsed));
+        }
+    };
+    send(Event::Prepared(Phase::Parse, None, elapsed));
+
+    let parts: &[u8] = match options.part {
+        Part::One => &[1],
+        Part::Two => &[2],
+        Part::Both => &[1, 2],
+    };
+
+    for &part in parts {
+        if part == 1 {
+            solve_part(1, events, || S::part_1(&parsed));
+        } else {
+            solve_part(2, events, || S::part_2(&parsed));
+        }
+    }
+
+    if let Ok(Some(runs)) = bench.recv() {
+        let mut rows = vec![("parse", timing::bench(runs, || S::parse(&input)))];
+        for &part in parts {
+            let stats = if part == 1 {
+                timing::bench(runs, || S::part_1(&parsed))
+            } else {
+                timing::bench(runs, || S::part_2(&parsed))
+            };
+            rows.push((part_label(part), stats));
+        }
+        send(Event::Bench(rows));
+    }
+}
+
+/// Solves one part and reports its answer, whatever the answer's type
+fn solve_part<A: Into<Answer>>(
+    part: u8,
+    events: &Sender<Event>,
+    solve: impl FnOnce() -> Result<A, AocError>,
+) {
+    let _ = events.send(Event::Started(Phase::Part(part)));
+    let (result, elapsed) = measure(Phase::Part(part), events, || panics::catch(solve));
+    let answer = result
+        .map(Into::into)
+        .map_err(|e| Failure::new(&e, ExitStatus::for_solve_error(&e)));
+    let _ = events.send(Event::Solved(part, answer, elapsed));
+}
+
+/// Times `f`, and reports what it allocated when allocations are counted
+fn measure<T>(phase: Phase, events: &Sender<Event>, f: impl FnOnce() -> T) -> (T, Duration) {
+    let tracker = memory::track();
+    let (result, elapsed) = timing::time(f);
+    if let Some(tracker) = tracker {
+        let _ = events.send(Event::Allocated(phase, tracker.finish()));
+    }
+    (result, elapsed)
+}
+
+fn part_label(part: u8) -> &'static str {
+    if part == 1 {
+        "part 1"
+    } else {
+        "part 2"
+    }
+}
+
+/**
+Prints the duration of each phase, followed by their total and the peak RSS
+
+When allocations are counted, each phase also shows its number of allocations,
+the bytes they requested and the peak of live bytes.
+*/
+fn print_timings(timings: &[(&str, Duration)], memory: &[(&str, AllocStats)]) {
+    let total: Duration = timings.iter().map(|(_, elapsed)| *elapsed).sum();
+
+    println!();
+    if !memory.is_empty() {
+        println!(
+            "{}",
+            Color::Dim.wrap(&format!(
+                "{:<7} {:>10} {:>10} {:>12} {:>12}",
+                "phase", "time", "allocs", "allocated", "peak live"
+            ))
+        );
+    }
+    for (phase, elapsed) in timings {
+        let allocated = memory
+            .iter()
+            .find(|(name, _)| name == phase)
+            .map(|(_, stats)| {
+                format!(
+                    " {:>10} {:>12} {:>12}",
+                    stats.allocations,
+                    format_bytes(stats.bytes),
+                    format_bytes(stats.peak_bytes as u64)
+                )
+            })
+            .unwrap_or_default();
+        println!(
+            "{} {:>10}{allocated}",
+            Color::Dim.wrap(&format!("{phase:<7}")),
+            format_duration(*elapsed)
+        );
+    }
+    println!(
+        "{} {:>10}",
+        Color::Bold.wrap(&format!("{:<7}", "total")),
+        format_duration(total)
+    );
+    if let Some(rss) = memory::peak_rss() {
+        println!(
+            "{} {:>10}",
+            Color::Dim.wrap(&format!("{:<7}", "max rss")),
+            format_bytes(rss)
+        );
+    }
+}
+
+/// Prints the statistics of each benchmarked phase as a table
+fn print_bench(rows: &[(&str, Stats)]) {
+    println!();
+    println!(
+        "{}",
+        Color::Bold.wrap(&format!(
+            "Benchmark: {} runs after {} warmup runs",
+            rows[0].1.runs,
+            timing::WARMUP_RUNS
+        ))
+    );
+    println!(
+        "{}",
+        Color::Dim.wrap(&format!(
+            "{:<7} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "phase", "min", "median", "mean", "p95", "std dev"
+        ))
+    );
+    for (phase, stats) in rows {
+        println!(
+            "{:<7} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            phase,
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.mean),
+            format_duration(stats.p95),
+            format_duration(stats.std_dev),
+        );
+    }
+}
+
+/// Writes the benchmark results to [`BENCHMARK_FILE`] in the day directory `dir`, as Markdown
+fn save_bench(rows: &[(&str, Stats)], filename: &str, dir: &Path) {
+    let content = format!(
+        "Input: `{filename}`, {} warmup runs\n\n{}",
+        timing::WARMUP_RUNS,
+        timing::markdown_table(rows)
+    );
+
+    let path = dir.join(BENCHMARK_FILE);
+    if let Err(e) = fs::write(&path, content) {
+        eprintln!("Could not write {}", path.display());
+        print_chain(&error_chain(&e));
+        ExitStatus::Failed.exit();
+    }
+    eprintln!("Benchmark saved to {}", path.display());
+}
 
-            process::exit(1);
+/// Prints an error followed by its chain of sources
+fn print_chain(chain: &[ChainLink]) {
+    for (level, link) in chain.iter().enumerate() {
+        if level == 0 {
+            eprintln!("Error: {}", link.message);
+        } else {
+            eprintln!("Caused by ({level}): {}", link.message);
         }
     }
 }
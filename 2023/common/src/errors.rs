@@ -1,7 +1,10 @@
+use crate::timing::format_duration;
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
 use std::io;
 use std::num::{ParseFloatError, ParseIntError};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /**
 Represents errors that can occur when reading and parsing input files.
@@ -17,6 +20,52 @@ pub enum InputError {
     EmptyFile,
     /// The file content doesn't match the expected format.
     InvalidFormat(String),
+    /// A grid cell could not be parsed from its character.
+    InvalidCell {
+        /// The row of the cell, from 1
+        row: usize,
+        /// The column of the cell, from 1
+        column: usize,
+        /// The character that could not be parsed
+        character: char,
+        /// Why it could not be parsed
+        message: String,
+    },
+    /// A number in the input could not be parsed, or does not fit its type.
+    InvalidNumber {
+        /// The line of the number, from 1
+        line: usize,
+        /// The column where the number starts, from 1
+        column: usize,
+        /// The number as written, with its sign
+        number: String,
+        /// Why it could not be parsed
+        source: ParseIntError,
+    },
+    /// No input file of that name exists in any of the places searched.
+    NotFound {
+        /// The file name that was asked for
+        name: String,
+        /// Every path that was tried, in order
+        tried: Vec<PathBuf>,
+        /// The sample files found instead, if any
+        samples: Vec<String>,
+    },
+}
+
+impl InputError {
+    /// Returns the name of this variant, for machine-readable reports
+    #[must_use]
+    pub fn variant(&self) -> &'static str {
+        match self {
+            InputError::IoError(_) => "IoError",
+            InputError::EmptyFile => "EmptyFile",
+            InputError::InvalidFormat(_) => "InvalidFormat",
+            InputError::InvalidCell { .. } => "InvalidCell",
+            InputError::InvalidNumber { .. } => "InvalidNumber",
+            InputError::NotFound { .. } => "NotFound",
+        }
+    }
 }
 
 impl Display for InputError {
@@ -25,6 +74,39 @@ impl Display for InputError {
             InputError::IoError(err) => write!(f, "I/o error: {err}"),
             InputError::EmptyFile => write!(f, "File is empty"),
             InputError::InvalidFormat(msg) => write!(f, "Invalid format: {msg}"),
+            InputError::InvalidCell {
+                row,
+                column,
+                character,
+                message,
+            } => write!(
+                f,
+                "Invalid cell {character:?} at row {row}, column {column}: {message}"
+            ),
+            InputError::InvalidNumber {
+                line,
+                column,
+                number,
+                ..
+            } => write!(
+                f,
+                "Invalid number '{number}' at line {line}, column {column}"
+            ),
+            InputError::NotFound {
+                name,
+                tried,
+                samples,
+            } => {
+                write!(f, "Input file '{name}' not found, tried:")?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                if samples.is_empty() {
+                    write!(f, "\nNo sample files either")
+                } else {
+                    write!(f, "\nSample files: {}", samples.join(", "))
+                }
+            }
         }
     }
 }
@@ -33,6 +115,7 @@ impl Error for InputError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             InputError::IoError(err) => Some(err),
+            InputError::InvalidNumber { source, .. } => Some(source),
             _ => None,
         }
     }
@@ -63,6 +146,37 @@ pub enum AocError {
     ParseFloat(ParseFloatError),
     /// A custom error with a message.
     Custom(String),
+    /// The solver did not finish within the time limit.
+    TimedOut(Duration),
+    /// The solver stopped early because it was asked to (see [`crate::cancel`]).
+    Cancelled,
+    /// The solver panicked (see [`crate::panics`]).
+    Panic {
+        /// The panic message
+        message: String,
+        /// Where it panicked, as `file:line:column`
+        location: Option<String>,
+    },
+    /// The part has not been solved yet, as in a freshly generated day.
+    NotImplemented,
+}
+
+impl AocError {
+    /// Returns the name of this variant, for machine-readable reports
+    #[must_use]
+    pub fn variant(&self) -> &'static str {
+        match self {
+            AocError::Input(_) => "Input",
+            AocError::Parse(_) => "Parse",
+            AocError::ParseInt(_) => "ParseInt",
+            AocError::ParseFloat(_) => "ParseFloat",
+            AocError::Custom(_) => "Custom",
+            AocError::TimedOut(_) => "TimedOut",
+            AocError::Cancelled => "Cancelled",
+            AocError::Panic { .. } => "Panic",
+            AocError::NotImplemented => "NotImplemented",
+        }
+    }
 }
 
 impl Display for AocError {
@@ -73,6 +187,17 @@ impl Display for AocError {
             AocError::ParseInt(err) => write!(f, "Integer parse error: {err}"),
             AocError::ParseFloat(err) => write!(f, "Float parse error: {err}"),
             AocError::Custom(msg) => write!(f, "{msg}"),
+            AocError::TimedOut(limit) => write!(f, "Timed out after {}", format_duration(*limit)),
+            AocError::Cancelled => write!(f, "Cancelled"),
+            AocError::Panic {
+                message,
+                location: Some(location),
+            } => write!(f, "Panicked at {location}: {message}"),
+            AocError::Panic {
+                message,
+                location: None,
+            } => write!(f, "Panicked: {message}"),
+            AocError::NotImplemented => write!(f, "Not implemented yet"),
         }
     }
 }
@@ -136,3 +261,64 @@ let result: Result<(), _> = Err(err);
 pub fn custom_error<S: Into<String>>(msg: S) -> AocError {
     AocError::Custom(msg.into())
 }
+
+/**
+One level of an error's source chain, as reported by [`error_chain`]
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainLink {
+    /// The variant (or type) of the error at this level
+    pub variant: String,
+    /// The error's own message
+    pub message: String,
+}
+
+/**
+Flattens an error and its sources into a list of [`ChainLink`]s
+
+The first element is `err` itself, followed by each `source()` in turn.
+`AocError` and `InputError` levels are named after their variant, I/O errors
+after their kind, and any other error after its type.
+
+# Examples
+
+```
+use common::errors::{error_chain, AocError, InputError};
+
+let err = AocError::from(InputError::EmptyFile);
+let chain = error_chain(&err);
+assert_eq!(chain[0].variant, "Input");
+assert_eq!(chain[1].variant, "EmptyFile");
+assert_eq!(chain[1].message, "File is empty");
+```
+*/
+pub fn error_chain(err: &(dyn Error + 'static)) -> Vec<ChainLink> {
+    let mut chain = Vec::new();
+    let mut current = Some(err);
+
+    while let Some(err) = current {
+        chain.push(ChainLink {
+            variant: variant_name(err),
+            message: err.to_string(),
+        });
+        current = err.source();
+    }
+    chain
+}
+
+/// Names the variant (or type) of an error that is only known as `dyn Error`
+fn variant_name(err: &(dyn Error + 'static)) -> String {
+    if let Some(err) = err.downcast_ref::<AocError>() {
+        err.variant().to_string()
+    } else if let Some(err) = err.downcast_ref::<InputError>() {
+        err.variant().to_string()
+    } else if let Some(err) = err.downcast_ref::<io::Error>() {
+        format!("{:?}", err.kind())
+    } else if err.is::<ParseIntError>() {
+        "ParseIntError".to_string()
+    } else if err.is::<ParseFloatError>() {
+        "ParseFloatError".to_string()
+    } else {
+        "Error".to_string()
+    }
+}
@@ -0,0 +1,119 @@
+use crate::errors::{AocError, InputError};
+use std::io;
+use std::process;
+
+/**
+The status a day binary exits with, one per class of failure
+
+| Code | Status         | Meaning                                                     |
+|------|----------------|-------------------------------------------------------------|
+| 0    | `Success`      | Every answer was computed, and none contradicts a known one |
+| 1    | `Failed`       | A part returned an error, or anything not covered below     |
+| 2    | `Usage`        | The command line is invalid                                 |
+| 3    | `InputMissing` | The input is missing or unreadable (`NotFound`, `IoError`)  |
+| 4    | `InputEmpty`   | The input is empty (`InputError::EmptyFile`)                |
+| 5    | `InputInvalid` | Parsing the input failed, or it is not UTF-8                |
+| 6    | `WrongAnswer`  | An answer differs from the known answer                     |
+| 7    | `TimedOut`     | A phase ran out of time (`AocError::TimedOut`)              |
+| 8    | `Panicked`     | The solver panicked (`AocError::Panic`)                     |
+| 9    | `Unsolved`     | A part is not implemented yet (`AocError::NotImplemented`)  |
+
+Scripts can tell "the input is missing" (3) from "the solver is broken"
+(1 or 8). When a run fails in several ways, such as part 1 panicking and part 2
+giving a wrong answer, the first failure decides the status. An unsolved part
+only decides it when nothing else failed, so it can't hide a real failure.
+
+# Examples
+
+```
+use common::errors::{AocError, InputError};
+use common::exit::ExitStatus;
+
+let err = AocError::from(InputError::EmptyFile);
+assert_eq!(ExitStatus::for_parse_error(&err).code(), 4);
+
+let err = AocError::Parse("no start tile".to_string());
+assert_eq!(ExitStatus::for_parse_error(&err), ExitStatus::InputInvalid);
+assert_eq!(ExitStatus::for_solve_error(&err), ExitStatus::Failed);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    Success,
+    Failed,
+    Usage,
+    InputMissing,
+    InputEmpty,
+    InputInvalid,
+    WrongAnswer,
+    TimedOut,
+    Panicked,
+    Unsolved,
+}
+
+impl ExitStatus {
+    /// The process exit code
+    #[must_use]
+    pub fn code(self) -> u8 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::Failed => 1,
+            ExitStatus::Usage => 2,
+            ExitStatus::InputMissing => 3,
+            ExitStatus::InputEmpty => 4,
+            ExitStatus::InputInvalid => 5,
+            ExitStatus::WrongAnswer => 6,
+            ExitStatus::TimedOut => 7,
+            ExitStatus::Panicked => 8,
+            ExitStatus::Unsolved => 9,
+        }
+    }
+
+    /// The status for an error while reading the input
+    #[must_use]
+    pub fn for_input_error(err: &InputError) -> Self {
+        match err {
+            // Content that is not UTF-8 was read, but can't be parsed
+            InputError::IoError(e) if e.kind() == io::ErrorKind::InvalidData => {
+                ExitStatus::InputInvalid
+            }
+            InputError::IoError(_) | InputError::NotFound { .. } => ExitStatus::InputMissing,
+            InputError::EmptyFile => ExitStatus::InputEmpty,
+            InputError::InvalidFormat(_)
+            | InputError::InvalidCell { .. }
+            | InputError::InvalidNumber { .. } => ExitStatus::InputInvalid,
+        }
+    }
+
+    /// The status for an error returned by `Solution::parse`: the input is at fault
+    #[must_use]
+    pub fn for_parse_error(err: &AocError) -> Self {
+        Self::for_error(err, ExitStatus::InputInvalid)
+    }
+
+    /// The status for an error returned by a part: the solver is at fault
+    #[must_use]
+    pub fn for_solve_error(err: &AocError) -> Self {
+        Self::for_error(err, ExitStatus::Failed)
+    }
+
+    /// The status for `err`, or `otherwise` when its variant doesn't decide it
+    fn for_error(err: &AocError, otherwise: Self) -> Self {
+        match err {
+            AocError::Input(err) => Self::for_input_error(err),
+            // A solver is only cancelled once it has run out of time
+            AocError::TimedOut(_) | AocError::Cancelled => ExitStatus::TimedOut,
+            AocError::Panic { .. } => ExitStatus::Panicked,
+            AocError::NotImplemented => ExitStatus::Unsolved,
+            AocError::Parse(_)
+            | AocError::ParseInt(_)
+            | AocError::ParseFloat(_)
+            | AocError::Custom(_) => otherwise,
+        }
+    }
+
+    /// Ends the process with this status
+    pub fn exit(self) -> ! {
+        process::exit(self.code().into())
+    }
+}
@@ -0,0 +1,402 @@
+use crate::errors::InputError;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A position in a [`Grid`]: `x` is the column and `y` the row, from the top left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    #[must_use]
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// The point `dx` columns and `dy` rows away, unless that is left of or above the origin
+    #[must_use]
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+/// Offsets of the 4 orthogonal neighbors: up, right, down, left
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the 8 neighbors, diagonals included, clockwise from the top left
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/**
+A rectangular grid of cells, stored row by row in one `Vec`
+
+Cells are addressed by [`Point`], with checked access through [`Grid::get`] or
+indexing that panics out of bounds. Neighbor iterators only yield points
+inside the grid, so solvers don't have to clamp coordinates themselves.
+
+# Examples
+
+```
+use common::grid::{Grid, Point};
+
+let grid = Grid::from_rows(vec![
+    vec!['#', '.', '.'],
+    vec!['.', 'S', '.'],
+]).unwrap();
+assert_eq!((grid.width(), grid.height()), (3, 2));
+
+let start = grid.find(&'S').unwrap();
+assert_eq!(start, Point::new(1, 1));
+assert_eq!(grid.neighbors8(start).count(), 5);
+assert_eq!(grid.get(Point::new(3, 0)), None);
+
+let walls = grid.map(|&c| c == '#');
+assert!(walls[Point::new(0, 0)]);
+assert_eq!(grid.transpose().to_string(), "#.\n.S\n..\n");
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /**
+    Builds a grid from its rows
+
+    # Errors
+    Returns `InputError::InvalidFormat` if the rows have different lengths
+    */
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, InputError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(InputError::InvalidFormat(format!(
+                "Grid rows have inconsistent lengths: row {} has {} cells, expected {width}",
+                y + 1,
+                rows[y].len()
+            )));
+        }
+
+        Ok(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// Builds a `width` × `height` grid, with `f` giving the cell at each point
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the grid has no cells
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether `point` is inside the grid
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    /// The cell at `point`, or `None` outside the grid
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    /// The cell at `point`, mutably, or `None` outside the grid
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.y * self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    /// The up to 4 orthogonal neighbors of `point` inside the grid: up, right, down, left
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &ORTHOGONAL)
+    }
+
+    /// The up to 8 neighbors of `point` inside the grid, diagonals included
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &ALL_AROUND)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| point.offset(dx, dy))
+            .filter(|&neighbor| self.contains(neighbor))
+    }
+
+    /**
+    The cells of row `y`, from left to right
+
+    # Panics
+    If `y` is not a row of the grid
+    */
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(
+            y < self.height,
+            "row {y} out of a grid {} high",
+            self.height
+        );
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The rows, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero size, which an empty grid has no rows of anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /**
+    The cells of column `x`, from top to bottom
+
+    # Panics
+    If `x` is not a column of the grid
+    */
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} out of a grid {} wide",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The columns, from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (Point::new(i % self.width, i / self.width), cell))
+    }
+
+    /// The position of the first cell, row by row, for which `predicate` holds
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find_map(|(point, cell)| predicate(cell).then_some(point))
+    }
+
+    /// The positions of every cell for which `predicate` holds, row by row
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter_map(move |(point, cell)| predicate(cell).then_some(point))
+    }
+
+    /// The position of the first cell equal to `value`, row by row
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    /// A grid of the same size, with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /**
+    A grid of the same size, with `f` applied to every cell, unless it fails
+
+    # Errors
+    Returns the first error returned by `f`, row by row
+    */
+    pub fn try_map<U, E>(&self, f: impl FnMut(&T) -> Result<U, E>) -> Result<Grid<U>, E> {
+        Ok(Grid {
+            cells: self.cells.iter().map(f).collect::<Result<_, _>>()?,
+            width: self.width,
+            height: self.height,
+        })
+    }
+
+    /// The grid with its rows and columns swapped
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn clockwise
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let bottom = self.height.saturating_sub(1);
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, bottom - p.x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn counterclockwise
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let right = self.width.saturating_sub(1);
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(right - p.y, p.x)].clone()
+        })
+    }
+
+    /// The grid mirrored left to right
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let right = self.width.saturating_sub(1);
+        Self::from_fn(self.width, self.height, |p| {
+            self[Point::new(right - p.x, p.y)].clone()
+        })
+    }
+
+    /// The grid mirrored top to bottom
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let bottom = self.height.saturating_sub(1);
+        Self::from_fn(self.width, self.height, |p| {
+            self[Point::new(p.x, bottom - p.y)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} out of a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} out of a {width}x{height} grid"))
+    }
+}
+
+/// One line per row, with the cells side by side
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]).unwrap()
+    }
+
+    #[test]
+    fn test_rotations_and_flips() {
+        let grid = grid();
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(
+            grid.rotate_clockwise().rotate_clockwise(),
+            grid.flip_horizontal().flip_vertical()
+        );
+    }
+
+    #[test]
+    fn test_neighbors_rows_and_columns() {
+        let grid = grid();
+        let corner: Vec<Point> = grid.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(corner, [Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Point::new(1, 0)).count(), 5);
+        assert_eq!(grid.neighbors4(Point::new(1, 1)).count(), 3);
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        let vowels: Vec<Point> = grid.positions(|c| "aeiou".contains(*c)).collect();
+        assert_eq!(vowels, [Point::new(0, 0), Point::new(1, 1)]);
+
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+        let empty = Grid::<u8>::from_rows(Vec::new()).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.rows().count(), 0);
+    }
+}
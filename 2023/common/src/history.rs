@@ -0,0 +1,304 @@
+use crate::errors::InputError;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the file, next to the inputs, that records every answer produced
+pub const HISTORY_FILE: &str = "history";
+
+/**
+One answer produced by a successful run
+
+The input is identified by the hash of its contents, so renaming or moving an
+input file keeps its history, and editing it starts a new one.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// When the answer was produced, in UTC, as `2023-12-08T06:12:45Z`
+    pub timestamp: String,
+    /// [`hash`] of the input contents, in hexadecimal
+    pub input_hash: String,
+    /// The input file name, or `stdin`, for reading the history
+    pub input: String,
+    pub part: u8,
+    /// The git revision of the code that produced the answer, when known
+    pub revision: Option<String>,
+    pub answer: String,
+}
+
+/**
+The answers a day produced over time, oldest first
+
+The history file has one tab-separated line per answer: timestamp, input hash,
+input name, part, git revision (`-` when unknown) and answer. Newlines, tabs
+and backslashes in answers are escaped, so a grid answer stays on one line
+(tabs shown as spaces):
+
+```text
+2023-12-08T06:12:45Z  3f1e7c0a9d2b4e61  input  1  a1b2c3d        20659
+2023-12-09T18:40:02Z  3f1e7c0a9d2b4e61  input  1  e4f5a6b-dirty  20660
+```
+
+The runner appends to it after every successful run, and warns when an answer
+differs from the last one recorded for the same input and part: shared code
+that changed under a solved day shows up right away.
+
+# Examples
+
+```
+use common::history::{hash, History};
+
+let history = History::parse("2023-12-08T06:12:45Z\tab12\tinput\t1\t-\t42\n").unwrap();
+let last = history.last("ab12", 1).unwrap();
+assert_eq!(last.answer, "42");
+assert_eq!(last.revision, None);
+assert!(history.last("ab12", 2).is_none());
+
+assert_ne!(hash("0 3 6\n"), hash("0 3 7\n"));
+```
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /**
+    Parses the contents of a history file
+
+    # Errors
+    Returns `InputError::InvalidFormat`, naming the line, if a line does not
+    have the six fields or its part is not `1` or `2`
+    */
+    pub fn parse(content: &str) -> Result<Self, InputError> {
+        let mut entries = Vec::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let invalid = |msg: &str| {
+                InputError::InvalidFormat(format!("{HISTORY_FILE} line {}: {msg}", i + 1))
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.splitn(6, '\t').collect();
+            let [timestamp, input_hash, input, part, revision, answer] = fields[..] else {
+                return Err(invalid("expected 6 tab-separated fields"));
+            };
+            let part = match part {
+                "1" => 1,
+                "2" => 2,
+                other => return Err(invalid(&format!("invalid part '{other}'"))),
+            };
+
+            entries.push(Entry {
+                timestamp: timestamp.to_string(),
+                input_hash: input_hash.to_string(),
+                input: input.to_string(),
+                part,
+                revision: (revision != "-").then(|| revision.to_string()),
+                answer: unescape(answer),
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    /**
+    Loads the history file at `path`
+
+    A missing file is not an error: nothing has been recorded yet.
+
+    # Errors
+    Returns an error if the file exists but cannot be read or parsed
+    */
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, InputError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The history file of an input file: the one next to it
+    #[must_use]
+    pub fn path_for(input: impl AsRef<Path>) -> PathBuf {
+        input
+            .as_ref()
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(HISTORY_FILE)
+    }
+
+    /// The most recent entry for the input with this hash and `part`, if any
+    #[must_use]
+    pub fn last(&self, input_hash: &str, part: u8) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.input_hash == input_hash && entry.part == part)
+    }
+
+    /// Every entry, oldest first
+    #[must_use]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /**
+    Appends `entries` to the history file at `path`, creating it if needed
+
+    # Errors
+    Returns an error if the file cannot be opened or written
+    */
+    pub fn append(path: impl AsRef<Path>, entries: &[Entry]) -> io::Result<()> {
+        let mut lines = String::new();
+        for entry in entries {
+            let revision = entry.revision.as_deref().unwrap_or("-");
+            lines.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{revision}\t{}\n",
+                entry.timestamp,
+                entry.input_hash,
+                entry.input,
+                entry.part,
+                escape(&entry.answer)
+            ));
+        }
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(lines.as_bytes())
+    }
+}
+
+/**
+A 64-bit FNV-1a hash of `content`, in hexadecimal
+
+FNV is not cryptographic, but it is stable across Rust versions and platforms,
+unlike `std`'s `DefaultHasher`, and plenty to tell puzzle inputs apart.
+*/
+#[must_use]
+pub fn hash(content: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = content.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/**
+The git revision checked out in `dir`, such as `a1b2c3d` or `a1b2c3d-dirty`
+
+Returns `None` when git is not installed or `dir` is not in a repository.
+*/
+#[must_use]
+pub fn git_revision(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    let revision = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !revision.is_empty()).then_some(revision)
+}
+
+/// `time` in UTC, as `2023-12-08T06:12:45Z`
+#[must_use]
+pub fn timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    // Howard Hinnant's `civil_from_days`, for days since 1970-01-01
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_hash_and_timestamp() {
+        // Reference values of 64-bit FNV-1a
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        let time = UNIX_EPOCH + Duration::from_secs(1_701_929_565);
+        assert_eq!(timestamp(time), "2023-12-07T06:12:45Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(951_782_400);
+        assert_eq!(timestamp(leap_day), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn test_multiline_answers_round_trip() {
+        let entry = Entry {
+            timestamp: "2023-12-10T00:00:00Z".to_string(),
+            input_hash: hash("x"),
+            input: "sample_1".to_string(),
+            part: 2,
+            revision: Some("a1b2c3d".to_string()),
+            answer: "#.\\\n.#\t".to_string(),
+        };
+
+        let path = std::env::temp_dir().join(format!("aoc-history-{}", std::process::id()));
+        History::append(&path, std::slice::from_ref(&entry)).unwrap();
+        History::append(&path, std::slice::from_ref(&entry)).unwrap();
+        let history = History::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(history.entries().len(), 2);
+        assert_eq!(history.last(&entry.input_hash, 2), Some(&entry));
+        assert!(History::parse("2023\tab\tinput\t3\t-\t1\n").is_err());
+    }
+}
@@ -1,10 +1,441 @@
 use crate::errors::InputError;
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use crate::grid::{Grid, Point};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where each marker character of a grid appears, in reading order
+pub type Markers = Vec<(char, Point)>;
+
+/**
+The contents of a puzzle input, read into memory once
+
+`Input` separates reading an input from parsing it: the file is read a single
+time, and the same buffer can then be viewed as lines, paragraphs, a grid, and
+so on. It offers the same shapes as `InputReader`, which reads a fresh copy of
+the file on every call.
+
+An `Input` does not have to come from a file: it can wrap text that is already
+in memory, or be read from any reader such as stdin. [`InputSource`] names
+where an input comes from, for code that should not care.
+
+# Examples
+
+```no_run
+use common::input::Input;
+
+let input = Input::read("input").unwrap();
+let lines = input.as_lines().unwrap();
+let grid = input.as_char_grid().unwrap();
+assert_eq!(lines.len(), grid.height());
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Input {
+    content: String,
+}
+
+impl Input {
+    /**
+    Reads the file at `path` into memory
+
+    # Errors
+    This function will return an error if:
+    * The file cannot be read (doesn't exist or insufficient permissions)
+    * The file content is not valid UTF-8
+    * The file is empty
+    */
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, InputError> {
+        InputSource::from(path.as_ref()).read()
+    }
+
+    /**
+    Reads everything from `reader` into memory
+
+    # Errors
+    This function will return an error if:
+    * Reading fails
+    * The content is not valid UTF-8
+    * There is nothing to read
+
+    # Examples
+
+    ```
+    use common::input::Input;
+
+    let input = Input::from_reader("0 3 6 9\n1 3 6 10\n".as_bytes()).unwrap();
+    assert_eq!(input.as_lines().unwrap().len(), 2);
+    ```
+    */
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, InputError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Self::from_content(content)
+    }
+
+    /**
+    Reads the whole of stdin into memory
+
+    # Errors
+    Same as [`Input::from_reader`]
+    */
+    pub fn from_stdin() -> Result<Self, InputError> {
+        InputSource::Stdin.read()
+    }
+
+    /**
+    Wraps text that is already in memory
+
+    # Errors
+    Returns `InputError::EmptyFile` if the text is empty, as for any other input
+    */
+    pub fn new(content: impl Into<String>) -> Result<Self, InputError> {
+        Self::from_content(content.into())
+    }
+
+    /// The one place an `Input` is built, so every source is checked the same way
+    fn from_content(content: String) -> Result<Self, InputError> {
+        if content.is_empty() {
+            return Err(InputError::EmptyFile);
+        }
+        Ok(Self { content })
+    }
+
+    /// Returns the whole input, newlines included
+    #[must_use]
+    pub fn as_string(&self) -> &str {
+        &self.content
+    }
+
+    /**
+    Splits the input into lines, without their line endings
+
+    # Errors
+    Returns `InputError::EmptyFile` if the input has no lines
+    */
+    pub fn as_lines(&self) -> Result<Vec<String>, InputError> {
+        let lines: Vec<String> = self.content.lines().map(String::from).collect();
+
+        if lines.is_empty() {
+            return Err(InputError::EmptyFile);
+        }
+        Ok(lines)
+    }
+
+    /// Joins every line of the input into one continuous string
+    #[must_use]
+    pub fn as_single_line(&self) -> String {
+        self.content.lines().collect()
+    }
+
+    /**
+    Splits the input into paragraphs, i.e. groups of lines separated by blank lines
+
+    # Errors
+    Returns `InputError::EmptyFile` if the input only contains whitespace
+    */
+    pub fn as_paragraphs(&self) -> Result<Vec<String>, InputError> {
+        let paragraphs: Vec<String> = self
+            .content
+            .split("\n\n")
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        if paragraphs.is_empty() {
+            return Err(InputError::EmptyFile);
+        }
+        Ok(paragraphs)
+    }
+
+    /**
+    Turns the input into a grid of characters, one row per line
+
+    Use [`Grid::map`] or [`Grid::try_map`] to turn the characters into the
+    puzzle's own cell type.
+
+    # Errors
+    This function will return an error if:
+    * The input has no lines
+    * The grid is not rectangular (lines have different lengths)
+    */
+    pub fn as_char_grid(&self) -> Result<Grid<char>, InputError> {
+        let lines = self.as_lines()?;
+        Grid::from_rows(lines.iter().map(|line| line.chars().collect()).collect())
+    }
+
+    /**
+    Parses the input into a grid of cells, one row per line and one cell per character
+
+    # Errors
+    This function will return an error if:
+    * The input has no lines
+    * A character is not a valid cell, naming its row, column and the reason
+    * The grid is not rectangular (lines have different lengths)
+
+    # Examples
+
+    ```
+    use common::grid::Grid;
+    use common::input::Input;
+
+    #[derive(Debug, PartialEq)]
+    enum Cell {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Cell {
+        type Error = String;
+
+        fn try_from(c: char) -> Result<Self, String> {
+            match c {
+                '.' => Ok(Cell::Open),
+                '#' => Ok(Cell::Wall),
+                _ => Err("expected '.' or '#'".to_string()),
+            }
+        }
+    }
+
+    let grid: Grid<Cell> = Input::new("#.\n.#\n").unwrap().as_grid().unwrap();
+    assert_eq!(grid.row(0), [Cell::Wall, Cell::Open]);
+
+    let err = Input::new("#.\n.x\n").unwrap().as_grid::<Cell>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid cell 'x' at row 2, column 2: expected '.' or '#'"
+    );
+    ```
+    */
+    pub fn as_grid<T>(&self) -> Result<Grid<T>, InputError>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        Ok(self.as_grid_with_markers(&[])?.0)
+    }
+
+    /**
+    Parses the input into a grid of cells like [`Input::as_grid`], also returning
+    where each of the `markers` characters appears
+
+    Markers are cells like any other, such as the `S` start tile of a maze; their
+    positions are listed in reading order, so a solver doesn't have to search the
+    grid for them.
+
+    # Errors
+    Same as [`Input::as_grid`]
+
+    # Examples
+
+    ```
+    use common::grid::{Grid, Point};
+    use common::input::Input;
+
+    let (grid, markers): (Grid<char>, _) =
+        Input::new(".S.\n..E\n").unwrap().as_grid_with_markers(&['S', 'E']).unwrap();
+    assert_eq!(markers, [('S', Point::new(1, 0)), ('E', Point::new(2, 1))]);
+    assert_eq!(grid[markers[0].1], 'S');
+    ```
+    */
+    pub fn as_grid_with_markers<T>(
+        &self,
+        markers: &[char],
+    ) -> Result<(Grid<T>, Markers), InputError>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        let mut found = Vec::new();
+        let mut rows = Vec::new();
+
+        for (y, line) in self.as_lines()?.iter().enumerate() {
+            let mut row = Vec::with_capacity(line.len());
+            for (x, character) in line.chars().enumerate() {
+                if markers.contains(&character) {
+                    found.push((character, Point::new(x, y)));
+                }
+                let cell = T::try_from(character).map_err(|e| InputError::InvalidCell {
+                    row: y + 1,
+                    column: x + 1,
+                    character,
+                    message: e.to_string(),
+                })?;
+                row.push(cell);
+            }
+            rows.push(row);
+        }
+
+        Ok((Grid::from_rows(rows)?, found))
+    }
+}
+
+/**
+Where a puzzle input comes from: a file, a string, stdin or an embedded byte slice
+
+Each source reads into an [`Input`] with [`InputSource::read`], and offers the
+same shapes as `Input` directly, by reading it and asking the `Input` for them. Tests can then solve a
+sample without a file on disk, or without depending on the current directory
+by embedding the file at compile time:
+
+```
+use common::input::InputSource;
+
+let source = InputSource::Text("1abc2\npqr3stu8vwx\n".to_string());
+assert_eq!(source.as_lines().unwrap(), ["1abc2", "pqr3stu8vwx"]);
+
+// Relative to the source file, as with `include_str!`
+let source = InputSource::Bytes(include_bytes!("../Cargo.toml"));
+assert!(source.as_string().unwrap().contains("[package]"));
+assert_eq!(source.to_string(), "embedded input");
+```
+
+Every source is read whole, and fails the same way on an empty input or
+invalid UTF-8.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A file, read when needed
+    File(PathBuf),
+    /// Text already in memory
+    Text(String),
+    /// The standard input, which can only be read once
+    Stdin,
+    /// Bytes built into the binary, typically with `include_bytes!`
+    Bytes(&'static [u8]),
+}
+
+impl InputSource {
+    /**
+    Reads the whole input into memory
+
+    # Errors
+    This function will return an error if:
+    * The file or stdin cannot be read
+    * The content is not valid UTF-8
+    * The input is empty
+    */
+    pub fn read(&self) -> Result<Input, InputError> {
+        match self {
+            InputSource::File(path) => Input::from_content(fs::read_to_string(path)?),
+            InputSource::Text(text) => Input::new(text.as_str()),
+            InputSource::Stdin => Input::from_reader(io::stdin().lock()),
+            InputSource::Bytes(bytes) => Input::from_reader(*bytes),
+        }
+    }
+
+    /**
+    Reads the whole input, newlines included
+
+    # Errors
+    Same as [`InputSource::read`]
+    */
+    pub fn as_string(&self) -> Result<String, InputError> {
+        Ok(self.read()?.content)
+    }
+
+    /**
+    Reads the input as lines, without their line endings
+
+    # Errors
+    Same as [`InputSource::read`]
+    */
+    pub fn as_lines(&self) -> Result<Vec<String>, InputError> {
+        self.read()?.as_lines()
+    }
+
+    /**
+    Reads the input as one continuous string, without newlines
+
+    # Errors
+    Same as [`InputSource::read`]
+    */
+    pub fn as_single_line(&self) -> Result<String, InputError> {
+        Ok(self.read()?.as_single_line())
+    }
+
+    /**
+    Reads the input as paragraphs, i.e. groups of lines separated by blank lines
+
+    # Errors
+    Same as [`InputSource::read`], or if the input only contains whitespace
+    */
+    pub fn as_paragraphs(&self) -> Result<Vec<String>, InputError> {
+        self.read()?.as_paragraphs()
+    }
+
+    /**
+    Reads the input as a grid of characters, one row per line
+
+    # Errors
+    Same as [`InputSource::read`], or if the grid is not rectangular
+    */
+    pub fn as_char_grid(&self) -> Result<Grid<char>, InputError> {
+        self.read()?.as_char_grid()
+    }
+
+    /**
+    Reads the input as a grid of cells parsed from their characters
+
+    # Errors
+    Same as [`Input::as_grid`], or if the input cannot be read
+    */
+    pub fn as_grid<T>(&self) -> Result<Grid<T>, InputError>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        self.read()?.as_grid()
+    }
+
+    /**
+    Reads the input as a grid of cells, with the positions of the `markers`
+
+    # Errors
+    Same as [`Input::as_grid_with_markers`], or if the input cannot be read
+    */
+    pub fn as_grid_with_markers<T>(
+        &self,
+        markers: &[char],
+    ) -> Result<(Grid<T>, Markers), InputError>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        self.read()?.as_grid_with_markers(markers)
+    }
+}
+
+/// Where the input comes from, as shown in messages
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Text(_) => write!(f, "text input"),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Bytes(_) => write!(f, "embedded input"),
+        }
+    }
+}
+
+impl From<PathBuf> for InputSource {
+    fn from(path: PathBuf) -> Self {
+        InputSource::File(path)
+    }
+}
+
+impl From<&Path> for InputSource {
+    fn from(path: &Path) -> Self {
+        InputSource::File(path.to_path_buf())
+    }
+}
 
 /**
 A struct that provides different ways to read and parse input files
+
+Each method reads the file as an [`InputSource::File`], so it behaves exactly
+like the same method of [`Input`].
 */
 pub struct InputReader;
 
@@ -32,18 +463,15 @@ impl InputReader {
 
     # Examples
 
-    ```
+    ```no_run
     use common::input::InputReader;
 
-    assert_eq!(InputReader::as_string(path), );
+    let result = InputReader::as_string("input");
+    assert!(result.is_ok());
     ```
     */
     pub fn as_string(path: impl AsRef<Path>) -> Result<String, InputError> {
-        let content = fs::read_to_string(path)?;
-        if content.is_empty() {
-            return Err(InputError::EmptyFile);
-        }
-        Ok(content)
+        InputSource::from(path.as_ref()).as_string()
     }
 
     /**
@@ -68,22 +496,15 @@ impl InputReader {
 
     # Examples
 
-    ```
+    ```no_run
     use common::input::InputReader;
 
-    assert_eq!(InputReader::as_lines(path), );
+    let result = InputReader::as_lines("input");
+    assert!(result.is_ok());
     ```
     */
     pub fn as_lines(path: impl AsRef<Path>) -> Result<Vec<String>, InputError> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        let lines: Vec<String> = reader.lines().collect::<Result<Vec<_>, _>>()?;
-
-        if lines.is_empty() {
-            return Err(InputError::EmptyFile);
-        }
-        Ok(lines)
+        InputSource::from(path.as_ref()).as_lines()
     }
 
     /**
@@ -119,7 +540,7 @@ impl InputReader {
 
     # Examples
     ```no_run
-    use your_crate::InputReader;
+    use common::input::InputReader;
 
     let content = InputReader::as_single_line("input.txt").unwrap();
     // Now we have a single continuous string to work with
@@ -130,18 +551,9 @@ impl InputReader {
         println!("Found pattern AABB in the sequence!");
     }
     ```
-
-    # Examples
-
-    ```
-    use common::input::InputReader;
-
-    assert_eq!(InputReader::as_single_line(path), );
-    ```
     */
     pub fn as_single_line(path: impl AsRef<Path>) -> Result<String, InputError> {
-        let content = Self::as_string(path)?;
-        Ok(content.lines().collect::<String>())
+        InputSource::from(path.as_ref()).as_single_line()
     }
 
     /**
@@ -177,7 +589,7 @@ impl InputReader {
 
     # Examples
     ```no_run
-    use your_crate::InputReader;
+    use common::input::InputReader;
 
     let paragraphs = InputReader::as_paragraphs("input.txt").unwrap();
     assert!(paragraphs.len() >= 1); // At least one paragraph
@@ -189,69 +601,150 @@ impl InputReader {
         }
     }
     ```
+    */
+    pub fn as_paragraphs(path: impl AsRef<Path>) -> Result<Vec<String>, InputError> {
+        InputSource::from(path.as_ref()).as_paragraphs()
+    }
+
+    /**
+    Reads a file into a grid of characters
+
+    Converts the input file into a [`Grid`] of characters, useful for problems
+    involving maps, mazes, or any grid-based challenges. Each line in the file
+    becomes a row in the grid.
+
+    # Arguments
+    * `path` - Any type that can be converted into a Path
+
+    # Returns
+    A grid of characters, as wide as the lines and as high as their number
+
+    # Errors
+    This function will return an error if:
+    * The file cannot be opened
+    * The file is empty
+    * The file contains invalid UTF-8
+    * The grid is not rectangular (lines have different lengths)
 
     # Examples
 
-    ```
+    ```no_run
     use common::input::InputReader;
 
-    assert_eq!(InputReader::as_paragraphs(path), );
+    let result = InputReader::as_char_grid("input");
+    assert!(result.is_ok());
     ```
     */
-    pub fn as_paragraphs(path: impl AsRef<Path>) -> Result<Vec<String>, InputError> {
-        let content = Self::as_string(path)?;
-        let paragraphs: Vec<String> = content
-            .split("\n\n")
-            .map(|p| p.trim().to_string())
-            .filter(|p| !p.is_empty())
-            .collect();
-
-        if paragraphs.is_empty() {
-            return Err(InputError::EmptyFile);
-        }
-        Ok(paragraphs)
+    pub fn as_char_grid(path: impl AsRef<Path>) -> Result<Grid<char>, InputError> {
+        InputSource::from(path.as_ref()).as_char_grid()
     }
 
     /**
-    Reads a file into a grid of characters
+    Reads a file into a grid of cells, each parsed from its character
 
-    Converts the input file into a two-dimensional vector of characters,
-    useful for problems involving maps, mazes, or any grid-based challenges.
-    Each line in the file becomes a row in the grid.
+    Any type implementing `TryFrom<char>` can be a cell, so a maze's tiles are
+    parsed straight into the puzzle's own enum. A character that isn't a valid
+    cell is reported with its row and column, both counted from 1.
 
     # Arguments
     * `path` - Any type that can be converted into a Path
 
-    # Returns
-    A vector of vectors of characters, representing the grid
-
     # Errors
     This function will return an error if:
     * The file cannot be opened
     * The file is empty
-    * The file contains invalid UTF-8
+    * A character is not a valid cell (`InputError::InvalidCell`)
     * The grid is not rectangular (lines have different lengths)
 
     # Examples
 
+    ```no_run
+    use common::grid::Grid;
+    use common::input::InputReader;
+
+    // Each cell holds the byte of its ASCII character
+    let bytes: Grid<u8> = InputReader::as_grid("input").unwrap();
     ```
+    */
+    pub fn as_grid<T>(path: impl AsRef<Path>) -> Result<Grid<T>, InputError>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        InputSource::from(path.as_ref()).as_grid()
+    }
+
+    /**
+    Reads a file into a grid of cells like [`InputReader::as_grid`], also
+    returning where each of the `markers` characters appears, in reading order
+
+    # Errors
+    Same as [`InputReader::as_grid`]
+
+    # Examples
+
+    ```no_run
+    use common::grid::Grid;
     use common::input::InputReader;
 
-    assert_eq!(InputReader::as_char_grid(path), );
+    let (maze, markers): (Grid<char>, _) =
+        InputReader::as_grid_with_markers("input", &['S']).unwrap();
+    let start = markers[0].1;
     ```
     */
-    pub fn as_char_grid(path: impl AsRef<Path>) -> Result<Vec<Vec<char>>, InputError> {
-        let lines = Self::as_lines(path)?;
-        let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
-
-        if let Some(first_len) = grid.first().map(std::vec::Vec::len) {
-            if grid.iter().any(|row| row.len() != first_len) {
-                return Err(InputError::InvalidFormat(
-                    "Grid rows have inconsistent lengths".to_string(),
-                ));
-            }
-        }
+    pub fn as_grid_with_markers<T>(
+        path: impl AsRef<Path>,
+        markers: &[char],
+    ) -> Result<(Grid<T>, Markers), InputError>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        InputSource::from(path.as_ref()).as_grid_with_markers(markers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(grid)
+    #[test]
+    fn test_from_reader() {
+        let input = Input::from_reader("#.\n.#\n".as_bytes()).unwrap();
+        assert_eq!(input.as_char_grid().unwrap().to_string(), "#.\n.#\n");
+
+        let err = Input::from_reader(io::empty()).unwrap_err();
+        assert!(matches!(err, InputError::EmptyFile));
+    }
+
+    #[test]
+    fn test_sources_offer_the_same_shapes() {
+        let text = "ab\ncd\n\nef\ngh\n";
+        let path = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::write(&path, text).unwrap();
+
+        let sources = [
+            InputSource::File(path.clone()),
+            InputSource::Text(text.to_string()),
+            InputSource::Bytes(b"ab\ncd\n\nef\ngh\n"),
+        ];
+        for source in &sources {
+            assert_eq!(source.as_string().unwrap(), text);
+            assert_eq!(source.as_lines().unwrap(), ["ab", "cd", "", "ef", "gh"]);
+            assert_eq!(source.as_single_line().unwrap(), "abcdefgh");
+            assert_eq!(source.as_paragraphs().unwrap(), ["ab\ncd", "ef\ngh"]);
+            assert!(source.as_char_grid().is_err());
+        }
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            InputSource::Text(String::new()).read(),
+            Err(InputError::EmptyFile)
+        ));
+        assert!(matches!(Input::new(""), Err(InputError::EmptyFile)));
+        assert!(matches!(
+            InputSource::Bytes(b"\xff\n").read(),
+            Err(InputError::IoError(_))
+        ));
     }
 }
@@ -0,0 +1,154 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+/**
+A minimal JSON value, used for machine-readable output
+
+Objects keep their keys in insertion order. Numbers are stored already
+formatted, so integers of any width are written exactly.
+
+# Examples
+
+```
+use common::json::Json;
+
+let value = Json::object([
+    ("day", Json::from(8_u8)),
+    ("answer", Json::from("20659")),
+    ("error", Json::Null),
+]);
+assert_eq!(value.to_string(), r#"{"day":8,"answer":"20659","error":null}"#);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Builds an object from `(key, value)` pairs, keeping their order
+    pub fn object<K: Into<String>>(entries: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Json::Object(entries.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Builds an array from any sequence of values
+    pub fn array(values: impl IntoIterator<Item = Json>) -> Self {
+        Json::Array(values.into_iter().collect())
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Number(n) => f.write_str(n),
+            Json::String(s) => write_escaped(f, s),
+            Json::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(entries) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Writes `s` as a quoted JSON string
+fn write_escaped(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if u32::from(c) < 0x20 => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Json {
+                fn from(value: $t) -> Self {
+                    Json::Number(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escapes_strings() {
+        let value = Json::from("say \"hi\"\n\\ \x1b[34m");
+        assert_eq!(value.to_string(), r#""say \"hi\"\n\\ \u001b[34m""#);
+    }
+
+    #[test]
+    fn test_nested_values() {
+        let value = Json::object([
+            ("parts", Json::array([Json::from(1_u8), Json::from(2_u8)])),
+            ("ok", Json::from(true)),
+            ("missing", Json::from(None::<u64>)),
+            ("big", Json::from(u128::MAX)),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"parts":[1,2],"ok":true,"missing":null,"big":340282366920938463463374607431768211455}"#
+        );
+    }
+}
@@ -1,4 +1,21 @@
+pub mod answer;
+pub mod answers;
+pub mod args;
+pub mod cancel;
 pub mod cli;
 pub mod color;
 pub mod errors;
+pub mod exit;
+pub mod grid;
+pub mod history;
 pub mod input;
+pub mod json;
+pub mod locate;
+pub mod log;
+pub mod memory;
+pub mod numbers;
+pub mod panics;
+pub mod scan;
+pub mod solution;
+pub mod timing;
+pub mod watch;
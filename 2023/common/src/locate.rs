@@ -0,0 +1,161 @@
+use crate::errors::InputError;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming a directory to look for input files in
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Start of the name of every sample input file, as in `sample_2`
+pub const SAMPLE_PREFIX: &str = "sample";
+
+/**
+The directories an input file is looked for in, in order
+
+These are the day's crate directory, when known, then the directory named by
+`AOC_INPUT_DIR`, when set, then the current directory. Searching the crate
+directory first lets `cargo run -p aoc-2023-8 -- 1` find `8/input` from the
+workspace root, and not only from the day's folder.
+*/
+#[must_use]
+pub fn search_dirs(crate_dir: Option<&Path>) -> Vec<PathBuf> {
+    let candidates = crate_dir
+        .map(Path::to_path_buf)
+        .into_iter()
+        .chain(
+            env::var_os(INPUT_DIR_VAR)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from),
+        )
+        .chain([PathBuf::from(".")]);
+
+    // The current directory is often the crate directory itself
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in candidates {
+        let same = |other: &PathBuf| match (dir.canonicalize(), other.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => dir == *other,
+        };
+        if !dirs.iter().any(same) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/**
+Finds the input file `name` in the first of `dirs` that has it
+
+An absolute `name` is used as it is.
+
+# Errors
+Returns `InputError::NotFound`, listing every path tried and the sample files
+found in `dirs`, if no directory has the file
+
+# Examples
+
+```
+use common::errors::InputError;
+use common::locate::locate;
+use std::path::PathBuf;
+
+let dirs = [PathBuf::from("/nonexistent"), PathBuf::from("src")];
+assert_eq!(locate("lib.rs", &dirs).unwrap(), PathBuf::from("src/lib.rs"));
+
+match locate("input", &dirs) {
+    Err(InputError::NotFound { tried, .. }) => assert_eq!(tried.len(), 2),
+    _ => unreachable!(),
+}
+```
+*/
+pub fn locate(name: &str, dirs: &[PathBuf]) -> Result<PathBuf, InputError> {
+    let tried: Vec<PathBuf> = if Path::new(name).is_absolute() {
+        vec![PathBuf::from(name)]
+    } else {
+        dirs.iter().map(|dir| dir.join(name)).collect()
+    };
+
+    if let Some(path) = tried.iter().find(|path| path.is_file()) {
+        return Ok(path.clone());
+    }
+
+    let mut samples: Vec<String> = Vec::new();
+    for sample in dirs
+        .iter()
+        .flat_map(|dir| sample_files(dir).unwrap_or_default())
+    {
+        if !samples.contains(&sample) {
+            samples.push(sample);
+        }
+    }
+    Err(InputError::NotFound {
+        name: name.to_string(),
+        tried,
+        samples,
+    })
+}
+
+/**
+The sample files in `dir`, such as `sample_1` or `sample_1_2`, in natural order
+
+Numbers in the names are compared by value, so `sample_10` comes after
+`sample_9`. Files with an extension, such as the `sample_1.expected` sidecars,
+are not samples.
+
+# Errors
+Returns an error if `dir` cannot be listed
+*/
+pub fn sample_files(dir: &Path) -> io::Result<Vec<String>> {
+    let mut samples = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let has_extension = Path::new(&name).extension().is_some();
+        if name.starts_with(SAMPLE_PREFIX) && !has_extension && entry.file_type()?.is_file() {
+            samples.push(name);
+        }
+    }
+
+    samples.sort_by_cached_key(|name| {
+        name.split('_')
+            .map(|chunk| chunk.parse::<u64>().map_err(|_| chunk.to_string()))
+            .collect::<Vec<_>>()
+    });
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn test_samples_in_natural_order_without_sidecars() {
+        let dir = env::temp_dir().join(format!("aoc-locate-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "sample_10",
+            "sample_2",
+            "sample_1_2",
+            "sample_2.expected",
+            "input",
+        ] {
+            fs::write(dir.join(name), "1\n").unwrap();
+        }
+
+        let samples = sample_files(&dir).unwrap();
+        assert_eq!(samples, ["sample_1_2", "sample_2", "sample_10"]);
+
+        match locate("sample_3", std::slice::from_ref(&dir)) {
+            Err(err @ InputError::NotFound { .. }) => {
+                let message = err.to_string();
+                assert!(message.contains(&dir.join("sample_3").display().to_string()));
+                assert!(message.ends_with("Sample files: sample_1_2, sample_2, sample_10"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
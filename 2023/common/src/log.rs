@@ -0,0 +1,139 @@
+use crate::color::Color;
+use std::fmt::Arguments;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How important a log message is, from most to least
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// Level shown when neither `-v` nor `-q` is given
+    pub const DEFAULT: Level = Level::Warn;
+
+    /**
+    The level reached by moving `verbosity` steps away from [`Level::DEFAULT`]
+
+    Each `-v` adds one step and each `-q` removes one. The result is clamped
+    between `Error` and `Trace`, so errors are always shown.
+
+    # Examples
+
+    ```
+    use common::log::Level;
+
+    assert_eq!(Level::from_verbosity(0), Level::Warn);
+    assert_eq!(Level::from_verbosity(2), Level::Debug);
+    assert_eq!(Level::from_verbosity(-5), Level::Error);
+    ```
+    */
+    #[must_use]
+    pub fn from_verbosity(verbosity: i8) -> Self {
+        match (Self::DEFAULT as i8).saturating_add(verbosity) {
+            i8::MIN..=1 => Level::Error,
+            2 => Level::Warn,
+            3 => Level::Info,
+            4 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    fn tag(self) -> String {
+        match self {
+            Level::Error => Color::Red.wrap("ERROR"),
+            Level::Warn => Color::Yellow.wrap("WARN "),
+            Level::Info => Color::Green.wrap("INFO "),
+            Level::Debug => Color::Blue.wrap("DEBUG"),
+            Level::Trace => Color::Dim.wrap("TRACE"),
+        }
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::DEFAULT as u8);
+
+/// Shows messages up to and including `level` from now on
+pub fn set_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether messages at `level` are currently shown
+#[inline]
+#[must_use]
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Writes one message to stderr; use the logging macros rather than calling this
+#[doc(hidden)]
+pub fn write(level: Level, args: Arguments<'_>) {
+    // Logging must never make a solver fail, so a closed stderr is ignored
+    let _ = writeln!(io::stderr().lock(), "{} {args}", level.tag());
+}
+
+/**
+Logs a message at the given [`Level`]
+
+The message is only formatted when the level is enabled, so disabled logging
+costs a single atomic load and its arguments are never evaluated. Output goes
+to stderr, keeping stdout for answers.
+
+# Examples
+
+```
+use common::log::{self, Level};
+
+log::set_level(Level::Info);
+common::log!(Level::Info, "parsed {} lines", 140);
+common::debug!("not shown, and {} is never called", expensive());
+
+fn expensive() -> u64 {
+    unreachable!()
+}
+```
+*/
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        if $crate::log::enabled(level) {
+            $crate::log::write(level, format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Logs a message at [`Level::Error`](crate::log::Level::Error)
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+/// Logs a message at [`Level::Warn`](crate::log::Level::Warn)
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+/// Logs a message at [`Level::Info`](crate::log::Level::Info)
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+/// Logs a message at [`Level::Debug`](crate::log::Level::Debug)
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+/// Logs a message at [`Level::Trace`](crate::log::Level::Trace)
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
@@ -0,0 +1,246 @@
+use crate::errors::InputError;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/**
+Every integer in `text`, in order, with `-` read as a sign
+
+A `-` is a sign when a digit follows it and no digit precedes it, so
+`x=-3..5, y=-12` gives `-3, 5, -12` while the range `3-5` gives `3, 5`.
+Anything that is neither a letter nor a digit separates numbers, whatever the
+spacing or punctuation.
+
+# Errors
+Returns `InputError::InvalidNumber`, with its line and column, for the first
+number that is glued to letters (such as `12a` or `4x1`), that doesn't fit in
+`T`, or that is negative while `T` is unsigned
+
+# Examples
+
+```
+use common::numbers;
+
+let numbers: Vec<i32> = numbers::signed("x=-3..5, y=-12 (3-5)").unwrap();
+assert_eq!(numbers, [-3, 5, -12, 3, 5]);
+
+let err = numbers::signed::<u8>("1 2\n3  300").unwrap_err();
+assert_eq!(err.to_string(), "Invalid number '300' at line 2, column 4");
+
+let err = numbers::signed::<u8>("1 2\n3 4x1").unwrap_err();
+assert_eq!(err.to_string(), "Invalid number '4x1' at line 2, column 3");
+```
+*/
+pub fn signed<T>(text: &str) -> Result<Vec<T>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    signed_at(text, 1, 1)
+}
+
+/**
+Every integer in `text`, in order, with `-` read as punctuation
+
+Suits inputs where a dash separates numbers, such as ranges like `1-3`, or
+where numbers can't be negative.
+
+# Errors
+Returns `InputError::InvalidNumber`, with its line and column, for the first
+number that is glued to letters or doesn't fit in `T`
+
+# Examples
+
+```
+use common::numbers;
+
+let numbers: Vec<u64> = numbers::unsigned("1-3 a: abcde\nCard  12: 41 | 83").unwrap();
+assert_eq!(numbers, [1, 3, 12, 41, 83]);
+```
+*/
+pub fn unsigned<T>(text: &str) -> Result<Vec<T>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    unsigned_at(text, 1, 1)
+}
+
+/**
+Every integer in `text`, a fragment of a larger input, as in [`signed`]
+
+`line` and `column` (both from 1) are where `text` starts in the input, so
+errors point into the input rather than into the fragment.
+
+# Errors
+Same as [`signed`]
+*/
+pub fn signed_at<T>(text: &str, line: usize, column: usize) -> Result<Vec<T>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    Ok(per_line(text, line, column, true)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/**
+Every integer in `text`, a fragment of a larger input, as in [`unsigned`]
+
+`line` and `column` (both from 1) are where `text` starts in the input.
+
+# Errors
+Same as [`unsigned`]
+
+# Examples
+
+```
+use common::numbers;
+
+let line = "Card 1: 41 48 | 83 4y";
+let (_, attempt) = line.split_once(" | ").unwrap();
+let err = numbers::unsigned_at::<u8>(attempt, 3, 17).unwrap_err();
+assert_eq!(err.to_string(), "Invalid number '4y' at line 3, column 20");
+```
+*/
+pub fn unsigned_at<T>(text: &str, line: usize, column: usize) -> Result<Vec<T>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    Ok(per_line(text, line, column, false)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/**
+The integers of each line of `text`, with `-` read as a sign, as in [`signed`]
+
+# Errors
+Same as [`signed`]
+*/
+pub fn signed_per_line<T>(text: &str) -> Result<Vec<Vec<T>>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    per_line(text, 1, 1, true)
+}
+
+/**
+The integers of each line of `text`, with `-` read as punctuation, as in [`unsigned`]
+
+# Errors
+Same as [`unsigned`]
+*/
+pub fn unsigned_per_line<T>(text: &str) -> Result<Vec<Vec<T>>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    per_line(text, 1, 1, false)
+}
+
+/// The integers of each line of `text`, which starts at `line` and `column` of the input
+fn per_line<T>(
+    text: &str,
+    line: usize,
+    column: usize,
+    signed: bool,
+) -> Result<Vec<Vec<T>>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(i, text)| {
+            // Only the first line of a fragment can start part way through a line
+            let column = if i == 0 { column } else { 1 };
+            in_line(text, line + i, column, signed)
+        })
+        .collect()
+}
+
+/**
+The integers of `text`, which starts at `line` and `column` of the input
+
+Numbers are read a word at a time, a word being a run of letters and digits: a
+word without digits is skipped, and one that mixes them is a malformed number.
+*/
+fn in_line<T>(text: &str, line: usize, column: usize, signed: bool) -> Result<Vec<T>, InputError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let is_word = |i: usize| chars.get(i).is_some_and(|(_, c)| c.is_alphanumeric());
+    let is_digit = |i: usize| chars.get(i).is_some_and(|(_, c)| c.is_ascii_digit());
+
+    let mut numbers = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !is_word(i) {
+            i += 1;
+            continue;
+        }
+
+        let mut end = i + 1;
+        while is_word(end) {
+            end += 1;
+        }
+        if !(i..end).any(is_digit) {
+            i = end;
+            continue;
+        }
+
+        let has_sign =
+            signed && is_digit(i) && i > 0 && chars[i - 1].1 == '-' && !(i > 1 && is_digit(i - 2));
+        let start = if has_sign { i - 1 } else { i };
+        let end_byte = chars.get(end).map_or(text.len(), |&(byte, _)| byte);
+        let number = &text[chars[start].0..end_byte];
+
+        let value = number.parse().map_err(|source| InputError::InvalidNumber {
+            line,
+            column: column + start,
+            number: number.to_string(),
+            source,
+        })?;
+        numbers.push(value);
+        i = end;
+    }
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_signs_spacing_and_errors() {
+        assert_eq!(
+            signed::<i64>("0 3  6 -9\n-1 -0 7").unwrap(),
+            [0, 3, 6, -9, -1, 0, 7]
+        );
+        assert_eq!(signed::<i8>("a-1-2--3").unwrap(), [-1, 2, -3]);
+        assert_eq!(unsigned::<u8>("a-1-2--3").unwrap(), [1, 2, 3]);
+        assert_eq!(
+            unsigned_per_line::<u8>("1\n\nno 2 3").unwrap(),
+            [vec![1], vec![], vec![2, 3]]
+        );
+        assert!(signed::<u32>("").unwrap().is_empty());
+
+        let err = signed::<u32>("é 4 -5").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid number '-5' at line 1, column 5");
+        assert!(err.source().is_some());
+
+        for glued in ["12a", "4x1", "x12", "1é"] {
+            let err = unsigned::<u32>(&format!("7 {glued}")).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Invalid number '{glued}' at line 1, column 3")
+            );
+        }
+        let err = signed_at::<i8>("1\n2 -3 -200", 4, 9).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid number '-200' at line 5, column 6");
+
+        let big = "99999999999999999999";
+        assert!(unsigned::<u64>(big).is_err());
+        assert_eq!(unsigned::<u128>(big).unwrap(), [99_999_999_999_999_999_999]);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::errors::AocError;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::Once;
+
+thread_local! {
+    /// Whether this thread is running inside [`catch`]
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Where the last panic caught on this thread happened
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/**
+Runs `f`, turning a panic into an [`AocError::Panic`]
+
+A solver that panics, say on an `unwrap()` of a malformed line, then fails
+like one returning an error: its message and location are reported in the
+usual error chain, and the run moves on to the next part. The default panic
+message is not printed for panics caught here; panics anywhere else still
+print it.
+
+# Examples
+
+```
+use common::errors::AocError;
+use common::panics;
+
+let result: Result<u32, AocError> = panics::catch(|| Ok("12a".parse::<u32>().unwrap()));
+match result {
+    Err(AocError::Panic { message, location }) => {
+        assert!(message.contains("InvalidDigit"));
+        assert!(location.is_some());
+    }
+    _ => unreachable!(),
+}
+
+assert_eq!(panics::catch(|| Ok::<_, AocError>(7)).unwrap(), 7);
+```
+*/
+pub fn catch<T>(f: impl FnOnce() -> Result<T, AocError>) -> Result<T, AocError> {
+    INSTALL_HOOK.call_once(install_hook);
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.unwrap_or_else(|payload| {
+        Err(AocError::Panic {
+            message: payload_message(payload.as_ref()),
+            location: LOCATION.take(),
+        })
+    })
+}
+
+/// Records the location of caught panics, and leaves the others to the previous hook
+fn install_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        if CATCHING.get() {
+            let location = info.location().map(ToString::to_string);
+            LOCATION.set(location);
+        } else {
+            previous(info);
+        }
+    }));
+}
+
+/// The message given to `panic!`, which is a `&str` or a `String` in practice
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_reports_message_and_location() {
+        let line = line!() + 1;
+        let result: Result<(), AocError> = catch(|| panic!("bad line {}", 3));
+
+        let err = result.unwrap_err();
+        assert_eq!(err.variant(), "Panic");
+        let message = err.to_string();
+        assert!(message.starts_with(&format!("Panicked at {}:{line}:", file!())));
+        assert!(message.ends_with(": bad line 3"));
+    }
+
+    #[test]
+    fn test_catch_passes_results_through() {
+        assert_eq!(catch(|| Ok::<_, AocError>("ok")).unwrap(), "ok");
+        assert_eq!(
+            catch(|| Err::<(), _>(AocError::Cancelled))
+                .unwrap_err()
+                .variant(),
+            "Cancelled"
+        );
+    }
+}
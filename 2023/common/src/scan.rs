@@ -0,0 +1,274 @@
+use crate::errors::InputError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/**
+Reads the fields of one line, laid out as in `pattern`
+
+`pattern` is literal text with a `{}` for each field, as in `format!`, and
+`{{`/`}}` for literal braces. Each field runs up to the first occurrence of the
+text that follows it in the pattern, or to the end of the line, and is trimmed
+before being parsed, so `Card {}: {}` reads both `Card 1: 41` and
+`Card   1:  41`. Fields are parsed into the types of the tuple `T`.
+
+# Errors
+Returns `InputError::InvalidFormat` if the pattern is malformed (two `{}` in a
+row cannot be told apart), if its number of fields differs from the tuple's,
+if the line doesn't match, naming the text expected and where, or if a field
+doesn't parse
+
+# Examples
+
+```
+use common::scan;
+
+let (node, left, right): (String, String, String) =
+    scan::line("{} = ({}, {})", "AAA = (BBB, CCC)").unwrap();
+assert_eq!((node.as_str(), left.as_str(), right.as_str()), ("AAA", "BBB", "CCC"));
+
+let (id, numbers): (u8, String) = scan::line("Card {}: {}", "Card   3:  1 21").unwrap();
+assert_eq!((id, numbers.as_str()), (3, "1 21"));
+
+let err = scan::line::<(String, String)>("{} = {}", "AAA - BBB").unwrap_err();
+assert_eq!(
+    err.to_string(),
+    "Invalid format: expected ' = ' after field 1 in 'AAA - BBB'"
+);
+```
+*/
+pub fn line<T: FromFields>(pattern: &str, line: &str) -> Result<T, InputError> {
+    Pattern::parse(pattern, T::COUNT)?
+        .read(line)
+        .map_err(InputError::InvalidFormat)
+}
+
+/**
+Reads every non-empty line of `text` as in [`line`]
+
+# Errors
+Same as [`line`], with the number (from 1) of the first line that fails
+
+# Examples
+
+```
+use common::scan;
+
+let moves: Vec<(char, u32)> = scan::lines("move {} by {}", "move R by 4\n\nmove U by 12\n").unwrap();
+assert_eq!(moves, [('R', 4), ('U', 12)]);
+
+let err = scan::lines::<(char, u32)>("move {} by {}", "move R by 4\nmove U by x").unwrap_err();
+assert_eq!(
+    err.to_string(),
+    "Invalid format: line 2: field 2 'x' of 'move U by x': invalid digit found in string"
+);
+```
+*/
+pub fn lines<T: FromFields>(pattern: &str, text: &str) -> Result<Vec<T>, InputError> {
+    let pattern = Pattern::parse(pattern, T::COUNT)?;
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            pattern
+                .read(line)
+                .map_err(|msg| InputError::InvalidFormat(format!("line {}: {msg}", i + 1)))
+        })
+        .collect()
+}
+
+/**
+Splits one line into the untyped fields of `pattern`, with where each starts
+
+For fields that hold more than one value, such as lists of numbers, to parse
+them with errors that point into the line (see [`crate::numbers::unsigned_at`]).
+
+# Errors
+Same as [`line`], except that fields are not parsed
+
+# Examples
+
+```
+use common::scan;
+
+let [id, numbers] = scan::fields("Card {}: {}", "Card   3:  1 21").unwrap();
+assert_eq!((id.text, id.column), ("3", 8));
+assert_eq!((numbers.text, numbers.column), ("1 21", 12));
+```
+*/
+pub fn fields<'a, const N: usize>(
+    pattern: &str,
+    line: &'a str,
+) -> Result<[Field<'a>; N], InputError> {
+    let fields = Pattern::parse(pattern, N)?
+        .split(line)
+        .map_err(InputError::InvalidFormat)?;
+    Ok(fields
+        .try_into()
+        .expect("the pattern has been checked to have N fields"))
+}
+
+/// A field of a line, as split by [`fields`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field<'a> {
+    /// The text of the field, trimmed
+    pub text: &'a str,
+    /// The column of its first character in the line, from 1
+    pub column: usize,
+}
+
+/**
+A tuple of types that fields can be parsed into
+
+Implemented for tuples of one to six `FromStr` types whose errors can be
+displayed.
+*/
+pub trait FromFields: Sized {
+    /// The number of fields
+    const COUNT: usize;
+
+    /**
+    Parses `fields`, of which there are exactly [`Self::COUNT`]
+
+    # Errors
+    Returns the index (from 0) of the first field that doesn't parse, and why
+    */
+    fn from_fields(fields: &[&str]) -> Result<Self, (usize, String)>;
+}
+
+macro_rules! impl_from_fields {
+    ($count:literal; $($index:tt $type:ident),+) => {
+        impl<$($type),+> FromFields for ($($type,)+)
+        where
+            $($type: FromStr, $type::Err: Display),+
+        {
+            const COUNT: usize = $count;
+
+            fn from_fields(fields: &[&str]) -> Result<Self, (usize, String)> {
+                Ok(($(
+                    fields[$index]
+                        .parse::<$type>()
+                        .map_err(|e| ($index, e.to_string()))?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_from_fields!(1; 0 A);
+impl_from_fields!(2; 0 A, 1 B);
+impl_from_fields!(3; 0 A, 1 B, 2 C);
+impl_from_fields!(4; 0 A, 1 B, 2 C, 3 D);
+impl_from_fields!(5; 0 A, 1 B, 2 C, 3 D, 4 E);
+impl_from_fields!(6; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+
+/// A pattern split into the literal text around its fields
+struct Pattern {
+    /// One more than the fields: the text before, between and after them
+    literals: Vec<String>,
+}
+
+impl Pattern {
+    /// Parses `pattern`, which must have `count` fields
+    fn parse(pattern: &str, count: usize) -> Result<Self, InputError> {
+        let invalid = |msg: &str| InputError::InvalidFormat(format!("pattern '{pattern}': {msg}"));
+
+        let mut literals = vec![String::new()];
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                    literals.last_mut().unwrap().push(c);
+                }
+                ('{', Some('}')) => {
+                    chars.next();
+                    if literals.len() > 1 && literals.last().unwrap().is_empty() {
+                        return Err(invalid("fields must be separated by some text"));
+                    }
+                    literals.push(String::new());
+                }
+                ('{' | '}', _) => return Err(invalid("unmatched brace, use '{{' or '}}'")),
+                _ => literals.last_mut().unwrap().push(c),
+            }
+        }
+
+        if literals.len() - 1 != count {
+            return Err(invalid(&format!(
+                "has {} fields, expected {count}",
+                literals.len() - 1
+            )));
+        }
+
+        Ok(Self { literals })
+    }
+
+    /// Splits `line` into its fields, describing the mismatch if it doesn't fit the pattern
+    fn split<'a>(&self, line: &'a str) -> Result<Vec<Field<'a>>, String> {
+        let (first, rest) = self.literals.split_first().unwrap();
+        let (last, between) = rest.split_last().unwrap();
+
+        // `text` starts where `remaining` does
+        let field = |text: &'a str, remaining: &str| {
+            let start = line.len() - remaining.len() + text.len() - text.trim_start().len();
+            Field {
+                text: text.trim(),
+                column: line[..start].chars().count() + 1,
+            }
+        };
+
+        let mut remaining = line
+            .strip_prefix(first.as_str())
+            .ok_or_else(|| format!("expected '{first}' at the start of '{line}'"))?;
+
+        let mut fields = Vec::with_capacity(between.len() + 1);
+        for (i, literal) in between.iter().enumerate() {
+            let (text, after) = remaining
+                .split_once(literal.as_str())
+                .ok_or_else(|| format!("expected '{literal}' after field {} in '{line}'", i + 1))?;
+            fields.push(field(text, remaining));
+            remaining = after;
+        }
+
+        let text = remaining
+            .strip_suffix(last.as_str())
+            .ok_or_else(|| format!("expected '{last}' at the end of '{line}'"))?;
+        fields.push(field(text, remaining));
+
+        Ok(fields)
+    }
+
+    /// Reads `line`, describing the mismatch if it doesn't fit the pattern
+    fn read<T: FromFields>(&self, line: &str) -> Result<T, String> {
+        let texts: Vec<&str> = self.split(line)?.iter().map(|field| field.text).collect();
+
+        T::from_fields(&texts)
+            .map_err(|(i, msg)| format!("field {} '{}' of '{line}': {msg}", i + 1, texts[i]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        let (a, b): (u32, String) = line("{{{}}} {}!", "{7} go!").unwrap();
+        assert_eq!((a, b.as_str()), (7, "go"));
+
+        assert!(line::<(u32, u32)>("{}{}", "12").is_err());
+        assert!(line::<(u32, u32)>("{} {", "1 2").is_err());
+        assert!(line::<(u32,)>("{} {}", "1 2").is_err());
+
+        let err = line::<(u32,)>("#{}", "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid format: expected '#' at the start of '1'"
+        );
+        let err = line::<(u32,)>("{}.", "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid format: expected '.' at the end of '1'"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use crate::answer::Answer;
+use crate::errors::AocError;
+use crate::input::Input;
+
+/**
+A day's solver: how to parse its input, and how to solve each part from it
+
+The input is parsed once into `Parsed`, which both parts then borrow, so
+parsing code is written a single time and can be shared between days. Each
+part has its own answer type, so part 1 can count with a `u32` while part 2
+needs a `u64`, or returns a `String`; the runner turns both into an
+[`Answer`] to display and check them.
+[`cli::run`](crate::cli::run) drives a `Solution` from the command line, and
+tests can call the methods directly on in-memory inputs.
+
+# Examples
+
+```
+use common::errors::AocError;
+use common::input::Input;
+use common::solution::Solution;
+
+struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = String;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        Ok(input
+            .as_lines()?
+            .iter()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_1(numbers: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        Ok(numbers.iter().sum())
+    }
+
+    fn part_2(numbers: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        let max = numbers.iter().max().ok_or("no numbers")?;
+        Ok(format!("max {max}"))
+    }
+}
+
+let numbers = Day1::parse(&Input::new("3\n4\n").unwrap()).unwrap();
+assert_eq!(Day1::part_1(&numbers).unwrap(), 7);
+assert_eq!(Day1::part_2(&numbers).unwrap(), "max 4");
+```
+*/
+pub trait Solution {
+    /// The puzzle input once parsed, shared by both parts
+    type Parsed;
+
+    /// What part 1 computes
+    type Answer1: Into<Answer>;
+
+    /// What part 2 computes
+    type Answer2: Into<Answer>;
+
+    /**
+    Turns the raw input into [`Self::Parsed`]
+
+    # Errors
+    Returns an error if the input does not have the expected format
+    */
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError>;
+
+    /**
+    Solves part 1 from the parsed input
+
+    # Errors
+    Returns an error if no answer can be computed
+    */
+    fn part_1(parsed: &Self::Parsed) -> Result<Self::Answer1, AocError>;
+
+    /**
+    Solves part 2 from the parsed input
+
+    # Errors
+    Returns an error if no answer can be computed
+    */
+    fn part_2(parsed: &Self::Parsed) -> Result<Self::Answer2, AocError>;
+}
@@ -0,0 +1,259 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Number of untimed runs performed by [`bench`] before measuring
+pub const WARMUP_RUNS: usize = 3;
+
+/**
+Runs `f` and measures how long it took
+
+# Examples
+
+```
+use common::timing;
+
+let (sum, elapsed) = timing::time(|| (1..=100).sum::<u32>());
+assert_eq!(sum, 5050);
+println!("took {}", timing::format_duration(elapsed));
+```
+*/
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/**
+Formats a duration with the unit that best fits its magnitude
+
+Durations below a microsecond are shown in whole nanoseconds, larger ones with
+two decimals in µs, ms or s.
+
+# Examples
+
+```
+use common::timing::format_duration;
+use std::time::Duration;
+
+assert_eq!(format_duration(Duration::from_nanos(812)), "812 ns");
+assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+```
+*/
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    let secs = duration.as_secs_f64();
+
+    if nanos < 1_000 {
+        format!("{nanos} ns")
+    } else if nanos < 1_000_000 {
+        format!("{:.2} µs", secs * 1e6)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", secs * 1e3)
+    } else {
+        format!("{secs:.2} s")
+    }
+}
+
+/**
+Parses a duration given on the command line, such as `500ms`, `30s` or `2m`
+
+A number without a unit is taken as seconds. Fractional values are allowed.
+
+# Examples
+
+```
+use common::timing::parse_duration;
+use std::time::Duration;
+
+assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+assert_eq!(parse_duration("soon"), None);
+```
+*/
+#[must_use]
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (value, unit) = text.split_at(split);
+
+    let value: f64 = value.parse().ok()?;
+    let secs = match unit {
+        "ms" => value / 1e3,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/**
+Summary statistics over a set of timing samples
+
+# Examples
+
+```
+use common::timing::Stats;
+use std::time::Duration;
+
+let samples = [3, 1, 2].map(Duration::from_millis);
+let stats = Stats::from_samples(&samples).unwrap();
+assert_eq!(stats.median, Duration::from_millis(2));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`, or `None` if there are none
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let runs = sorted.len();
+
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        // Nearest-rank percentile: the smallest sample covering 95% of the runs
+        let p95_rank = (runs * 95).div_ceil(100).max(1);
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let count = secs.len() as f64;
+        let mean = secs.iter().sum::<f64>() / count;
+        let variance = if runs > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            runs,
+            min: sorted[0],
+            max: sorted[runs - 1],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            p95: sorted[p95_rank - 1],
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/**
+Runs `f` [`WARMUP_RUNS`] times untimed, then `runs` times timed, and returns
+the statistics of the timed runs
+
+The result of every call is passed through [`black_box`] so the work cannot be
+optimized away.
+*/
+pub fn bench<T>(runs: usize, f: impl Fn() -> T) -> Stats {
+    for _ in 0..WARMUP_RUNS {
+        black_box(f());
+    }
+
+    let samples: Vec<Duration> = (0..runs.max(1))
+        .map(|_| {
+            let (result, elapsed) = time(&f);
+            black_box(result);
+            elapsed
+        })
+        .collect();
+
+    Stats::from_samples(&samples).expect("at least one run is always timed")
+}
+
+/**
+Renders benchmark results as a Markdown table
+
+All durations are given in microseconds so that tables from different days
+can be compared directly.
+*/
+#[must_use]
+pub fn markdown_table(rows: &[(&str, Stats)]) -> String {
+    let micros = |d: Duration| format!("{:.3}", d.as_secs_f64() * 1e6);
+
+    let mut table = String::from(
+        "| Phase | Runs | Min [µs] | Median [µs] | Mean [µs] | p95 [µs] | Std dev [µs] |\n\
+         |:---|---:|---:|---:|---:|---:|---:|\n",
+    );
+    for (phase, stats) in rows {
+        table.push_str(&format!(
+            "| `{phase}` | {} | {} | {} | {} | {} | {} |\n",
+            stats.runs,
+            micros(stats.min),
+            micros(stats.median),
+            micros(stats.mean),
+            micros(stats.p95),
+            micros(stats.std_dev),
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration_units() {
+        assert_eq!(format_duration(Duration::ZERO), "0 ns");
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_234)), "1.23 µs");
+        assert_eq!(format_duration(Duration::from_micros(45_600)), "45.60 ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50 s");
+    }
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("10"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("0.5ms"), Some(Duration::from_micros(500)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("-1s"), None);
+        assert_eq!(parse_duration("5 days"), None);
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples: Vec<Duration> = (1..=20).map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.runs, 20);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(20));
+        assert_eq!(stats.median, Duration::from_micros(10_500));
+        assert_eq!(stats.mean, Duration::from_micros(10_500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        // Sample standard deviation of 1..=20 is sqrt(35)
+        assert_eq!(stats.std_dev.as_micros(), 5_916);
+    }
+
+    #[test]
+    fn test_stats_single_and_empty() {
+        assert_eq!(Stats::from_samples(&[]), None);
+
+        let stats = Stats::from_samples(&[Duration::from_nanos(7)]).unwrap();
+        assert_eq!(stats.p95, Duration::from_nanos(7));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often [`Watcher::wait`] looks for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Size and modification time of every watched file, by path
+type Snapshot = BTreeMap<PathBuf, (u64, Option<SystemTime>)>;
+
+/**
+Notices when files change, by polling their size and modification time
+
+Watching a directory watches every file below it, except hidden files,
+editor backups ending in `~` and `target` directories. Polling needs no
+OS-specific notification service, and a few hundred files are cheap to check
+a few times per second.
+
+# Examples
+
+```no_run
+use common::watch::Watcher;
+
+let mut watcher = Watcher::new(["src", "input"]);
+loop {
+    let changed = watcher.wait();
+    println!("{} file(s) changed", changed.len());
+}
+```
+*/
+#[derive(Debug)]
+pub struct Watcher {
+    roots: Vec<PathBuf>,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    /// Starts watching the given files and directories, which don't have to exist yet
+    pub fn new<P: Into<PathBuf>>(roots: impl IntoIterator<Item = P>) -> Self {
+        let roots: Vec<PathBuf> = roots.into_iter().map(Into::into).collect();
+        let snapshot = snapshot(&roots);
+        Self { roots, snapshot }
+    }
+
+    /// The files created, modified or deleted since the last check
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let current = snapshot(&self.roots);
+        let mut changed: Vec<PathBuf> = current
+            .iter()
+            .filter(|(path, state)| self.snapshot.get(*path) != Some(state))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.snapshot
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        );
+        self.snapshot = current;
+        changed
+    }
+
+    /// Blocks until something changes, and returns what did
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            let changed = self.changed();
+            if !changed.is_empty() {
+                return changed;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+fn snapshot(roots: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for root in roots {
+        scan(root, &mut snapshot);
+    }
+    snapshot
+}
+
+/// Records the state of `path`, and of everything below it if it is a directory
+fn scan(path: &Path, snapshot: &mut Snapshot) {
+    // Files can disappear while being scanned, which is just another change
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_dir() {
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if !(name.starts_with('.') || name.ends_with('~') || name == "target") {
+                scan(&entry.path(), snapshot);
+            }
+        }
+    } else {
+        snapshot.insert(
+            path.to_path_buf(),
+            (metadata.len(), metadata.modified().ok()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn test_notices_created_modified_and_deleted_files() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let main = dir.join("src").join("main.rs");
+        fs::write(&main, "fn main() {}").unwrap();
+
+        let mut watcher = Watcher::new([&dir]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(dir.join("src").join(".main.rs.swp"), "ignored").unwrap();
+        let input = dir.join("input");
+        fs::write(&input, "1\n").unwrap();
+        assert_eq!(watcher.changed(), [input.as_path()]);
+
+        fs::write(&main, "fn main() { run(); }").unwrap();
+        fs::remove_file(&input).unwrap();
+        let mut changed = watcher.changed();
+        changed.sort();
+        assert_eq!(changed, [input, main]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
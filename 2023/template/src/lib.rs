@@ -1,3 +1,27 @@
 pub mod part_1;
 pub mod part_2;
 pub mod utils;
+
+use common::errors::AocError;
+use common::input::Input;
+use common::solution::Solution;
+
+pub struct Day<DAY>;
+
+impl Solution for Day<DAY> {
+    type Parsed = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, AocError> {
+        utils::parse_input(input)
+    }
+
+    fn part_1(lines: &Self::Parsed) -> Result<Self::Answer1, AocError> {
+        part_1::solve(lines)
+    }
+
+    fn part_2(lines: &Self::Parsed) -> Result<Self::Answer2, AocError> {
+        part_2::solve(lines)
+    }
+}
@@ -1,6 +1,5 @@
-use aoc_<YEAR>_<DAY>::{part_1, part_2};
-use common::cli;
+use aoc_<YEAR>_<DAY>::Day<DAY>;
 
 fn main() {
-    cli::run(part_1::solve, part_2::solve);
+    common::run!(Day<DAY>);
 }
@@ -1,5 +1,5 @@
 use common::errors::AocError;
 
-pub fn solve(filename: &str) -> Result<u64, AocError> {
-    Ok(42)
+pub fn solve(_lines: &[String]) -> Result<u64, AocError> {
+    Err(AocError::NotImplemented)
 }